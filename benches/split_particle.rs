@@ -0,0 +1,59 @@
+//! How long it takes to split a full particle tree, spawning every subparticle on the way.
+//!
+//! Run with `cargo bench --no-default-features`, so Bevy isn't dynamically linked. Save a baseline
+//! before a change with `-- --save-baseline before` and compare with `-- --baseline before`.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_jam_6::demo::particle::{Particle, SpawnParticle, headless_app, split_all_particles};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+/// A particle with `children` subparticles per particle, `depth` levels deep.
+fn particle_tree(depth: u32, children: usize) -> Particle {
+    let subparticles = if depth == 0 {
+        Vec::new()
+    } else {
+        (0..children)
+            .map(|i| Particle {
+                initial_velocity: 200.0 * Vec2::from_angle(TAU * i as f32 / children as f32),
+                ..particle_tree(depth - 1, children)
+            })
+            .collect()
+    };
+
+    Particle {
+        subparticles,
+        ..default()
+    }
+}
+
+fn spawned_tree(depth: u32) -> App {
+    let mut app = headless_app();
+    app.world_mut().trigger(SpawnParticle {
+        translation: Vec2::ZERO,
+        particle: particle_tree(depth, 3),
+        spawn_with_invincible: false,
+        show_arrows: true,
+        parent: None,
+    });
+    app.world_mut().flush();
+    app
+}
+
+fn split_particle_trees(c: &mut Criterion) {
+    let mut group = c.benchmark_group("split_particle");
+    for depth in [3, 5] {
+        group.bench_function(format!("depth {depth}"), |b| {
+            b.iter_batched(
+                || spawned_tree(depth),
+                |mut app| split_all_particles(app.world_mut()).unwrap(),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, split_particle_trees);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, screens::Screen};
+use crate::{Pause, asset_tracking::LoadResource, screens::Screen, settings::Settings};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MusicAssets>();
@@ -9,6 +9,10 @@ pub(super) fn plugin(app: &mut App) {
         PostUpdate,
         update_gameplay_music.run_if(in_state(Screen::Gameplay)),
     );
+    app.add_systems(
+        Update,
+        pause_gameplay_music.run_if(state_changed::<Pause>.or(resource_changed::<Settings>)),
+    );
 }
 
 #[derive(Asset, Resource, Clone, Reflect)]
@@ -45,6 +49,27 @@ pub fn gameplay_music(music_assets: &MusicAssets) -> impl Bundle {
     )
 }
 
+/// The music keeps playing in the pause menu unless [`Settings::pause_audio`] is on.
+fn gameplay_music_paused(pause: Pause, settings: &Settings) -> bool {
+    pause.0 && settings.pause_audio
+}
+
+/// Pausing the sink keeps its volume, so the global volume still applies on resume.
+fn pause_gameplay_music(
+    pause: Res<State<Pause>>,
+    settings: Res<Settings>,
+    sink_query: Query<&AudioSink, With<GameplayMusic>>,
+) {
+    let paused = gameplay_music_paused(*pause.get(), &settings);
+    for sink in sink_query.iter() {
+        if paused {
+            sink.pause();
+        } else {
+            sink.play();
+        }
+    }
+}
+
 fn update_gameplay_music(
     mut audio_query: Query<(Entity, &mut GameplayMusic, &AudioSink)>,
     music_assets: Res<MusicAssets>,
@@ -70,3 +95,38 @@ fn update_gameplay_music(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, state::app::StatesPlugin};
+
+    use super::*;
+
+    /// Whether the gameplay music is paused after pausing the game.
+    fn music_paused_in_pause_menu(pause_audio: bool) -> bool {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.insert_state(Pause(true));
+        app.insert_resource(Settings {
+            pause_audio,
+            ..default()
+        });
+        // An idle sink plays without an audio device.
+        let (sink, _output) = rodio::Sink::new_idle();
+        let music = app
+            .world_mut()
+            .spawn((GameplayMusic::Loop, AudioSink::new(sink)))
+            .id();
+
+        app.world_mut()
+            .run_system_once(pause_gameplay_music)
+            .unwrap();
+        app.world().get::<AudioSink>(music).unwrap().is_paused()
+    }
+
+    #[test]
+    fn music_pauses_only_when_enabled() {
+        assert!(music_paused_in_pause_menu(true));
+        assert!(!music_paused_in_pause_menu(false));
+    }
+}
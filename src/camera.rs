@@ -3,7 +3,7 @@ use bevy::{
         bloom::Bloom,
         tonemapping::{DebandDither, Tonemapping},
     },
-    image::{TextureFormatPixelInfo, Volume},
+    image::{ImageSampler, TextureFormatPixelInfo, Volume},
     prelude::*,
     render::{
         camera::RenderTarget,
@@ -15,15 +15,21 @@ use bevy::{
     window::{PrimaryWindow, WindowResized},
 };
 
+use crate::settings::Settings;
+
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<Letterboxing>();
 
     app.add_systems(Startup, spawn_camera);
     app.add_systems(Update, update_letterbox);
+    app.add_systems(
+        Update,
+        apply_retro_mode.run_if(resource_changed::<Settings>),
+    );
 }
 
 /// Type for storing 2D sizes.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Size<T> {
     pub width: T,
     pub height: T,
@@ -47,8 +53,65 @@ pub struct MainCamera;
 #[derive(Component)]
 pub struct GameplayNode;
 
+/// The retro mode renders one texture pixel for this many pixels per side of the resolution.
+const RETRO_PIXEL_SIZE: u32 = 4;
+
+/// The shape and resolution of the gameplay view, [`Letterboxing`] is computed from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LetterboxConfig {
+    pub aspect: Size<f32>,
+    /// The size of the texture the gameplay is rendered to.
+    pub resolution: Size<u32>,
+    /// How many world units are visible vertically.
+    pub world_height: f32,
+}
+
+impl Default for LetterboxConfig {
+    fn default() -> Self {
+        Self {
+            aspect: Size::new(16.0, 9.0),
+            resolution: Size::new(1920, 1080),
+            world_height: 720.0,
+        }
+    }
+}
+
+impl LetterboxConfig {
+    /// Checks that the resolution has the aspect ratio, otherwise the gameplay is stretched and
+    /// cursor positions don't match up with the world.
+    pub fn validate(&self) -> Result<(), String> {
+        let Self {
+            aspect,
+            resolution,
+            world_height,
+        } = *self;
+        if aspect.width <= 0.0 || aspect.height <= 0.0 || world_height <= 0.0 {
+            return Err(format!("non-positive size in {self:?}"));
+        }
+        if resolution.width == 0 || resolution.height == 0 {
+            return Err(format!("empty resolution in {self:?}"));
+        }
+
+        let resolution_aspect = resolution.width as f32 / resolution.height as f32;
+        let expected_aspect = aspect.width / aspect.height;
+        // A pixel of rounding is fine.
+        let tolerance = 1.0 / resolution.height.min(resolution.width) as f32;
+        if (resolution_aspect / expected_aspect - 1.0).abs() > tolerance {
+            return Err(format!(
+                "the resolution {}x{} doesn't have the aspect ratio {}:{}",
+                resolution.width, resolution.height, aspect.width, aspect.height
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The sizes the gameplay is rendered and shown with, see [`LetterboxConfig`].
 #[derive(Resource)]
 pub struct Letterboxing {
+    pub config: LetterboxConfig,
+    /// The current size of the gameplay texture, smaller than the resolution in the retro mode.
     pub texture_size: Size<u32>,
     pub projection_size: Size<f32>,
     pub aspect_ratio: Size<f32>,
@@ -56,12 +119,36 @@ pub struct Letterboxing {
 
 impl Default for Letterboxing {
     fn default() -> Self {
+        Self::new(LetterboxConfig::default())
+    }
+}
+
+impl Letterboxing {
+    /// Panics if the config is inconsistent, see [`LetterboxConfig::validate`].
+    pub fn new(config: LetterboxConfig) -> Self {
+        if let Err(err) = config.validate() {
+            panic!("Invalid letterbox config: {err}");
+        }
+
         Self {
-            texture_size: Size::new(1920, 1080),
-            projection_size: Size::new(1920.0 / 1.5, 1080.0 / 1.5),
-            aspect_ratio: Size::new(16.0, 9.0),
+            config,
+            texture_size: config.resolution,
+            projection_size: Size::new(
+                config.world_height * config.aspect.width / config.aspect.height,
+                config.world_height,
+            ),
+            aspect_ratio: config.aspect,
         }
     }
+
+    /// The size of the gameplay texture in the retro mode.
+    fn retro_texture_size(&self) -> Size<u32> {
+        let resolution = self.config.resolution;
+        Size::new(
+            (resolution.width / RETRO_PIXEL_SIZE).max(1),
+            (resolution.height / RETRO_PIXEL_SIZE).max(1),
+        )
+    }
 }
 
 /// Calulates the letterboxed size for a certain screen size
@@ -183,3 +270,131 @@ fn update_letterbox(
         node.height = Val::Px(size.height);
     }
 }
+
+/// Resizes the gameplay texture for the retro mode, only the pixel count changes and not what is
+/// visible.
+fn apply_retro_mode(
+    settings: Res<Settings>,
+    mut letterboxing: ResMut<Letterboxing>,
+    target_query: Query<&GameplayRenderTarget>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (texture_size, sampler) = if settings.retro_mode {
+        (letterboxing.retro_texture_size(), ImageSampler::nearest())
+    } else {
+        (letterboxing.config.resolution, ImageSampler::Default)
+    };
+
+    if letterboxing.texture_size == texture_size {
+        return;
+    }
+
+    let Ok(target) = target_query.single() else {
+        return;
+    };
+    let Some(image) = images.get_mut(&target.0) else {
+        return;
+    };
+
+    // Picking converts cursor positions with the texture size, so it has to stay in sync.
+    letterboxing.texture_size = texture_size;
+    image.sampler = sampler;
+    image.resize(Extent3d {
+        width: texture_size.width,
+        height: texture_size.height,
+        depth_or_array_layers: 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn retro_mode_shrinks_the_gameplay_texture() {
+        let mut world = World::new();
+        world.init_resource::<Letterboxing>();
+        world.insert_resource(Settings {
+            retro_mode: true,
+            ..default()
+        });
+
+        let mut images = Assets::<Image>::default();
+        let handle = images.add(Image::default());
+        world.insert_resource(images);
+        world.spawn(GameplayRenderTarget(handle.clone()));
+
+        world.run_system_once(apply_retro_mode).unwrap();
+
+        assert_eq!(
+            world.resource::<Letterboxing>().texture_size,
+            Size::new(480, 270)
+        );
+        let image = world.resource::<Assets<Image>>().get(&handle).unwrap();
+        assert_eq!(image.width(), 480);
+        assert_eq!(image.height(), 270);
+    }
+
+    #[test]
+    fn letterboxing_is_computed_from_the_config() {
+        let letterboxing = Letterboxing::default();
+        assert_eq!(letterboxing.texture_size, Size::new(1920, 1080));
+        assert_eq!(letterboxing.projection_size, Size::new(1280.0, 720.0));
+        assert_eq!(letterboxing.aspect_ratio, Size::new(16.0, 9.0));
+
+        let stretched = LetterboxConfig {
+            resolution: Size::new(1920, 1200),
+            ..default()
+        };
+        assert!(stretched.validate().is_err());
+        let odd = LetterboxConfig {
+            resolution: Size::new(1366, 768),
+            ..default()
+        };
+        assert!(odd.validate().is_ok());
+    }
+
+    /// Checks that `letterbox` keeps the aspect ratio and fits into the window.
+    fn assert_letterboxed(window: Size<f32>) -> Size<f32> {
+        let aspect_ratio = Size::new(16.0, 9.0);
+        let size = letterbox(window, aspect_ratio);
+
+        assert!(size.width <= window.width + 1e-3 && size.height <= window.height + 1e-3);
+        assert!(
+            (size.width * aspect_ratio.height - size.height * aspect_ratio.width).abs() < 1e-3,
+            "{size:?} doesn't have the aspect ratio"
+        );
+        // One side always fills the window.
+        assert!(
+            (size.width - window.width).abs() < 1e-3 || (size.height - window.height).abs() < 1e-3
+        );
+        size
+    }
+
+    #[test]
+    fn letterbox_fits_the_aspect_ratio_into_the_window() {
+        // Matching aspect ratio, no bars.
+        assert_eq!(
+            assert_letterboxed(Size::new(1600.0, 900.0)),
+            Size::new(1600.0, 900.0)
+        );
+        // Too wide, bars on the left and right.
+        assert_eq!(
+            assert_letterboxed(Size::new(2000.0, 900.0)),
+            Size::new(1600.0, 900.0)
+        );
+        // Too tall, bars on the top and bottom.
+        assert_eq!(
+            assert_letterboxed(Size::new(1600.0, 1200.0)),
+            Size::new(1600.0, 900.0)
+        );
+        // Tiny and empty windows.
+        assert_letterboxed(Size::new(1.0, 1.0));
+        assert_eq!(
+            assert_letterboxed(Size::new(0.0, 900.0)),
+            Size::new(0.0, 0.0)
+        );
+    }
+}
@@ -2,28 +2,37 @@
 //! purposes and should be replaced with your own game logic.
 //! Feel free to change the logic found here if you feel like tinkering around
 //! to get a feeling for the template.
+//!
+//! Gameplay events are logged at the debug level and quiet by default,
+//! run with `RUST_LOG=bevy_jam_6=debug` to include them in the log.
 
 use bevy::prelude::*;
 
+pub mod difficulty;
 mod drag_indicator;
-mod drag_input;
+pub mod drag_input;
 pub mod editor;
+mod ghost;
 mod killer;
 pub mod level;
-mod particle;
+pub mod particle;
 pub mod particle_effect;
 pub mod player;
+pub mod sandbox;
 pub mod time_scale;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
+        difficulty::plugin,
         editor::plugin,
-        level::plugin,
-        player::plugin,
+        level::LevelPlugin::default(),
+        player::PlayerPlugin::default(),
         drag_input::plugin,
         drag_indicator::plugin,
-        particle::plugin,
+        ghost::plugin,
+        particle::ParticlePlugin::default(),
+        sandbox::plugin,
         killer::plugin,
-        time_scale::plugin,
+        time_scale::TimeScalePlugin::default(),
     ));
 }
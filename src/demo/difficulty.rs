@@ -0,0 +1,138 @@
+//! A global difficulty, scaling how fast particles move without changing the levels, and how
+//! slow the slow motion is.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{particle::ParticleSpeedScale, time_scale::TimeScaleConfig};
+use crate::settings::Settings;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (apply_difficulty, apply_slow_motion).run_if(resource_changed::<Settings>),
+    );
+}
+
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    pub fn particle_speed_scale(self) -> ParticleSpeedScale {
+        ParticleSpeedScale(match self {
+            Self::Easy => 0.75,
+            Self::Normal => 1.0,
+            Self::Hard => 1.25,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    /// The next harder difficulty, or the easier one for negative `offset`, stopping at the ends.
+    pub fn step(self, offset: isize) -> Self {
+        let index = Self::ALL.iter().position(|&d| d == self).unwrap_or(1);
+        Self::ALL[index.saturating_add_signed(offset).min(Self::ALL.len() - 1)]
+    }
+}
+
+/// Particles already moving keep their speed, the new scale applies from the next spawn.
+fn apply_difficulty(settings: Res<Settings>, mut speed_scale: ResMut<ParticleSpeedScale>) {
+    speed_scale.set_if_neq(settings.difficulty.particle_speed_scale());
+}
+
+/// The time scale while the game is slowed down, for players who want more or less time to aim.
+///
+/// Saved as a plain number, clamped when loaded so a hand-edited value can't freeze or reverse
+/// the game.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "f32", into = "f32")]
+pub struct SlowMotion(pub f32);
+
+impl From<f32> for SlowMotion {
+    fn from(value: f32) -> Self {
+        if value.is_nan() {
+            return Self::default();
+        }
+        Self(value.clamp(Self::MIN, Self::MAX))
+    }
+}
+
+impl From<SlowMotion> for f32 {
+    fn from(slow_motion: SlowMotion) -> Self {
+        slow_motion.0
+    }
+}
+
+impl Default for SlowMotion {
+    fn default() -> Self {
+        Self(TimeScaleConfig::default().slowed)
+    }
+}
+
+impl SlowMotion {
+    const MIN: f32 = 0.05;
+    const MAX: f32 = 0.3;
+    const STEP: f32 = 0.05;
+
+    /// Faster by `offset` steps, or slower for negative `offset`, stopping at the ends.
+    pub fn step(self, offset: isize) -> Self {
+        let steps = (self.0 / Self::STEP).round() as isize + offset;
+        Self((steps as f32 * Self::STEP).clamp(Self::MIN, Self::MAX))
+    }
+}
+
+pub(super) fn apply_slow_motion(settings: Res<Settings>, mut config: ResMut<TimeScaleConfig>) {
+    if config.slowed != settings.slow_motion.0 {
+        config.slowed = settings.slow_motion.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn slow_motion_stays_in_range() {
+        let slow_motion = SlowMotion::default();
+        assert!((slow_motion.step(-1).0 - 0.05).abs() < 1e-6);
+        assert_eq!(slow_motion.step(-10).0, SlowMotion::MIN);
+        assert_eq!(slow_motion.step(10).0, SlowMotion::MAX);
+
+        let mut world = World::new();
+        world.init_resource::<TimeScaleConfig>();
+        world.insert_resource(Settings {
+            slow_motion: SlowMotion(0.2),
+            ..default()
+        });
+        world.run_system_once(apply_slow_motion).unwrap();
+        assert_eq!(world.resource::<TimeScaleConfig>().slowed, 0.2);
+    }
+
+    #[test]
+    fn loaded_slow_motion_is_clamped() {
+        let load = |ron: &str| ron::de::from_str::<Settings>(ron).unwrap().slow_motion;
+        assert_eq!(load("(slow_motion: 0.2)"), SlowMotion(0.2));
+        assert_eq!(load("(slow_motion: 0.0)"), SlowMotion(SlowMotion::MIN));
+        assert_eq!(load("(slow_motion: -1.0)"), SlowMotion(SlowMotion::MIN));
+        assert_eq!(load("(slow_motion: 5.0)"), SlowMotion(SlowMotion::MAX));
+        assert_eq!(load("(slow_motion: NaN)"), SlowMotion::default());
+
+        let saved = ron::ser::to_string(&Settings::default()).unwrap();
+        assert!(saved.contains("slow_motion:0.1"));
+    }
+}
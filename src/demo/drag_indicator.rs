@@ -78,7 +78,7 @@ fn update_drag_indicator(
 
         let (player, player_transform) = player_query.single().unwrap();
 
-        if !player.can_move {
+        if !player.can_move() {
             break 'blk false;
         }
 
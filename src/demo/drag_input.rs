@@ -1,6 +1,6 @@
 use crate::{
     AppSystems, PausableSystems, Pause, asset_tracking::LoadResource, audio::sound_effect,
-    screens::Screen,
+    key_bindings::KeyBindings, screens::Screen,
 };
 use bevy::prelude::*;
 
@@ -10,6 +10,7 @@ pub(super) fn plugin(app: &mut App) {
     app.load_resource::<DragInputAssets>();
 
     app.add_event::<StretchInputEvent>();
+    app.add_event::<StretchTooShortEvent>();
 
     app.add_systems(
         Update,
@@ -67,11 +68,17 @@ pub struct StretchInputEvent {
     pub vector: Vec2,
 }
 
+/// Sent when a drag is released below [`DragInputController::min_length`].
+#[derive(Event)]
+pub struct StretchTooShortEvent;
+
 fn record_drag_input(
     input: Res<ButtonInput<MouseButton>>,
+    key_bindings: Res<KeyBindings>,
     mut input_controller: ResMut<DragInputController>,
     window_query: Query<&Window>,
     mut events: EventWriter<StretchInputEvent>,
+    mut too_short_events: EventWriter<StretchTooShortEvent>,
     input_assets: Res<DragInputAssets>,
     drag_sound_query: Query<Entity, With<DragSound>>,
     mut commands: Commands,
@@ -79,13 +86,18 @@ fn record_drag_input(
     let window = window_query.single().unwrap();
 
     // Record initial mouse position.
-    if input.just_pressed(MouseButton::Left) {
+    if input.just_pressed(key_bindings.launch) {
         input_controller.initial_position = window.cursor_position();
+
+        // Restart the sound if the previous press wasn't released, e.g. when the window lost focus.
+        for drag_sound in drag_sound_query.iter() {
+            commands.entity(drag_sound).despawn();
+        }
         commands.spawn((DragSound, sound_effect(input_assets.drag_sound.clone())));
     }
 
     // Update vector of input controller.
-    if input.pressed(MouseButton::Left) {
+    if input.pressed(key_bindings.launch) {
         let vector = calculate_vector(input_controller.initial_position, window.cursor_position());
 
         let vector = vector.map(|v| {
@@ -100,7 +112,7 @@ fn record_drag_input(
     }
 
     // Send input event.
-    if input.just_released(MouseButton::Left) {
+    if input.just_released(key_bindings.launch) {
         for drag_sound in drag_sound_query.iter() {
             commands.entity(drag_sound).despawn();
         }
@@ -110,6 +122,8 @@ fn record_drag_input(
         if let Some(vector) = vector {
             if input_controller.min_length <= vector.length() {
                 events.write(StretchInputEvent { vector });
+            } else {
+                too_short_events.write(StretchTooShortEvent);
             }
         }
 
@@ -138,3 +152,50 @@ fn calculate_vector(
 fn reset_drag_input(mut input_controller: ResMut<DragInputController>) {
     *input_controller = DragInputController::default();
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+
+        app.init_resource::<DragInputAssets>();
+        app.init_resource::<DragInputController>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<ButtonInput<MouseButton>>();
+        app.add_event::<StretchInputEvent>();
+        app.add_event::<StretchTooShortEvent>();
+        app.world_mut().spawn(Window::default());
+
+        app
+    }
+
+    fn drag_sound_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&DragSound>()
+            .iter(app.world())
+            .count()
+    }
+
+    #[test]
+    fn pressing_again_keeps_a_single_drag_sound() {
+        let mut app = test_app();
+        let launch = app.world().resource::<KeyBindings>().launch;
+
+        for _ in 0..2 {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+            // A press without a release in between, as if the release was missed.
+            input.reset(launch);
+            input.clear();
+            input.press(launch);
+
+            app.world_mut().run_system_once(record_drag_input).unwrap();
+            assert_eq!(drag_sound_count(&mut app), 1);
+        }
+    }
+}
@@ -1,10 +1,14 @@
+#[cfg(not(target_family = "wasm"))]
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use bevy::{
-    ecs::{relationship::RelatedSpawner, spawn::SpawnWith},
     input::ButtonState,
     math::FloatOrd,
     picking::pointer::{Location, PointerAction, PointerId, PointerInput},
     prelude::*,
     render::camera::NormalizedRenderTarget,
+    sprite::Anchor,
     window::WindowEvent,
 };
 use bevy_inspector_egui::{
@@ -17,61 +21,145 @@ use crate::{
     camera::{GameplayCamera, GameplayRenderTarget, Letterboxing, Size, letterbox},
     demo::{
         level::{
-            SpawnRawLevel,
-            level_data::{LevelData, ObstacleData, ParticleData},
+            Level, PlaySource, SpawnRawLevel, level_bounds,
+            level_data::{
+                LevelData, LevelWarning, ObjectData, ObstacleData, ObstacleShape, ParticleData,
+            },
+            level_loading::LevelAssets,
+            objective::Objective,
+            obstacle,
+            trigger::{TriggerData, trigger_zone},
         },
         player::{PlayerConfig, player},
     },
     external::maybe::Maybe,
+    key_bindings::key_name,
     screens::{Screen, gameplay::SelectedLevel},
 };
 
-use super::particle::{
-    Particle, ParticleConfig, ParticleKind,
-    arrows::{Arrows, ArrowsAssets, ArrowsConfig, ArrowsOf, arrows},
+use alignment::{AlignmentKeys, align};
+use camera_controls::{camera_scale, set_camera_scale};
+use history::History;
+use particle_preview::SpawnParticlePreview;
+
+use super::{
+    killer::KillerLook,
+    particle::{Particle, ParticleKind, PopSounds, SpawnParticle, shape::ParticleShape},
 };
 
+mod alignment;
+mod audio_preview;
+mod camera_controls;
+mod history;
+pub mod level_changed;
+mod particle_preview;
+
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        audio_preview::plugin,
+        camera_controls::plugin,
+        level_changed::plugin,
+        particle_preview::plugin,
+    ));
+
     app.init_resource::<EditorState>();
     app.add_event::<EditorEvent>();
+    app.add_event::<LoadLevelIntoEditor>();
+    // Not limited to the editor, so levels can be sent to it before entering it.
+    app.add_systems(Update, load_level_into_editor);
 
+    app.add_observer(spawn_editor);
     app.add_observer(spawn_level_preview);
-    app.add_systems(
-        OnEnter(Screen::Editor),
-        |mut commands: Commands, mut editor_state: ResMut<EditorState>| {
-            commands.trigger(SpawnLevelPreview);
-            editor_state.editing = true;
-        },
-    );
     app.add_systems(
         EguiContextPass,
-        (editor_ui, refresh_level_preview)
+        (
+            editor_ui,
+            refresh_level_preview.run_if(not(resource_exists::<EditorSimulation>)),
+        )
             .chain()
             .run_if(in_state(Screen::Editor)),
     );
+    app.add_systems(OnExit(Screen::Editor), |mut commands: Commands| {
+        commands.remove_resource::<EditorSimulation>();
+    });
 
     app.add_systems(
         EguiContextPass,
         (
+            paste_into_editor,
             handle_editor_event_exit,
             handle_editor_event_print,
+            handle_editor_event_copy_object,
             handle_editor_event_load,
+            handle_editor_event_open,
             handle_editor_event_clear,
+            handle_editor_event_mirror,
+            handle_editor_event_rotate,
+            handle_editor_event_check,
+            handle_editor_event_simulate,
             handle_editor_event_play,
         )
             .chain()
             .run_if(in_state(Screen::Editor)),
     );
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        EguiContextPass,
+        (handle_editor_event_save, handle_editor_event_load_file).run_if(in_state(Screen::Editor)),
+    );
 
-    app.add_systems(Update, (object_placement).run_if(in_state(Screen::Editor)));
+    app.add_systems(
+        Update,
+        (
+            editor_shortcuts,
+            nudge_selected_objects,
+            object_placement,
+            test_from_cursor,
+            drag_velocity_handle,
+            drag_selected_object,
+            draw_velocity_handle,
+            draw_selection,
+            draw_alignment_guides,
+        )
+            .chain()
+            .run_if(in_state(Screen::Editor).and(not(resource_exists::<EditorSimulation>))),
+    );
 
-    app.add_systems(OnEnter(Screen::Editor), spawn_editor_pointer);
     app.add_systems(
         PreUpdate,
         editor_pointer_picking.run_if(in_state(Screen::Editor)),
     );
+}
+
+/// Sets up the editor, triggered when entering [`Screen::Editor`].
+#[derive(Event)]
+pub struct SpawnEditor;
 
-    app.add_observer(spawn_particle_preview);
+fn spawn_editor(
+    _: Trigger<SpawnEditor>,
+    mut editor_state: ResMut<EditorState>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameplayCamera>>,
+    mut commands: Commands,
+) {
+    editor_state.editing = true;
+    // Each session starts framed on the level, coming back from playing it keeps the view.
+    let (camera_translation, scale) = match editor_state.view_before_play.take() {
+        Some(view) => {
+            editor_state.mode = view.mode;
+            editor_state.selected = view.selected;
+            editor_state.selection = view.selection;
+            editor_state.velocity_target = view.velocity_target;
+            (view.camera_translation, view.camera_scale)
+        }
+        None => (Vec3::ZERO, 1.0),
+    };
+    // Gameplay moves the camera, e.g. when it follows the player.
+    if let Ok((mut camera_transform, mut projection)) = camera_query.single_mut() {
+        camera_transform.translation = camera_translation;
+        set_camera_scale(&mut projection, scale);
+    }
+    commands.spawn(editor_pointer());
+    commands.trigger(SpawnLevelPreview);
 }
 
 #[derive(Component)]
@@ -121,20 +209,33 @@ pub fn spawn_level_preview(
     commands.entity(level_preview).add_child(player);
 
     for (i, obstacle_data) in editor_state.level.obstacles.iter().enumerate() {
-        let obstacle_data = *obstacle_data;
-
-        let material = materials.add(obstacle_data.color);
-        let mesh = meshes.add(Rectangle::new(obstacle_data.width, obstacle_data.height));
+        let material = materials.add(obstacle_data.display_color());
+        let mesh = meshes.add(obstacle_data.mesh());
 
         let obstacle = commands
             .spawn((
-                obstacle_preview(obstacle_data.transform, material, mesh),
+                obstacle_preview(
+                    obstacle_data.transform,
+                    material,
+                    mesh,
+                    obstacle_data.is_killer,
+                ),
                 PreviewIndex::Obstacle(i),
             ))
             .observe(select)
             .id();
 
         commands.entity(level_preview).add_child(obstacle);
+
+        if let Some(label) = &obstacle_data.label {
+            let top = obstacle_data
+                .corners()
+                .iter()
+                .map(|corner| corner.y)
+                .fold(f32::MIN, f32::max);
+            let x = obstacle_data.transform.translation.x;
+            commands.spawn((label_tag(label, vec2(x, top)), ChildOf(level_preview)));
+        }
     }
 
     for (i, particle_data) in editor_state.level.particles.iter().enumerate() {
@@ -144,90 +245,25 @@ pub fn spawn_level_preview(
             particle: particle_data.particle.clone(),
             parent: Some(level_preview),
         });
-    }
-}
-
-#[derive(Event)]
-pub struct SpawnParticlePreview {
-    pub index: usize,
-    pub translation: Vec2,
-    pub particle: Particle,
-    pub parent: Option<Entity>,
-}
-
-fn spawn_particle_preview(
-    mut trigger: Trigger<SpawnParticlePreview>,
-    particle_config: Res<ParticleConfig>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    arrows_config: Res<ArrowsConfig>,
-    arrows_assets: Res<ArrowsAssets>,
-    mut commands: Commands,
-) {
-    commands
-        .spawn((
-            PreviewIndex::Particle(trigger.index),
-            particle_preview_bundle(
-                trigger.translation,
-                std::mem::take(&mut trigger.particle),
-                &particle_config,
-                meshes.as_mut(),
-                materials.as_mut(),
-                &arrows_config,
-                &arrows_assets,
-            ),
-            // The subparticle will have the same parent as the particle if it has a parent.
-            Maybe(trigger.parent.map(ChildOf)),
-        ))
-        .observe(select);
-}
 
-pub fn particle_preview_bundle(
-    translation: Vec2,
-    particle: Particle,
-    particle_config: &ParticleConfig,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
-    arrows_config: &ArrowsConfig,
-    arrows_assets: &ArrowsAssets,
-) -> impl Bundle {
-    let spawn_list = {
-        let arrows_config = *arrows_config;
-        let arrows_assets = arrows_assets.clone();
-        let particle = particle.clone();
-
-        move |spawner: &mut RelatedSpawner<ArrowsOf>| {
-            spawner.spawn(arrows(
-                translation,
-                &particle,
-                &arrows_config,
-                &arrows_assets,
-            ));
+        if let Some(label) = &particle_data.label {
+            let top = particle_data.spawn_position + Vec2::Y * particle_data.particle.radius;
+            commands.spawn((label_tag(label, top), ChildOf(level_preview)));
         }
-    };
-
-    (
-        Arrows::spawn(SpawnWith(spawn_list)),
-        particle_preview(translation, particle, particle_config, meshes, materials),
-    )
+    }
 }
 
-pub fn particle_preview(
-    translation: Vec2,
-    particle: Particle,
-    particle_config: &ParticleConfig,
-    meshes: &mut Assets<Mesh>,
-    materials: &mut Assets<ColorMaterial>,
-) -> impl Bundle {
-    let mesh = meshes.add(Circle::new(particle.radius));
-    let material = materials.add(particle.color);
+const LABEL_TAG_MARGIN: f32 = 6.0;
+const LABEL_TAG_Z: f32 = 10.0;
 
+/// An editor-only tag showing the label of an object, `top` is the top of the object.
+fn label_tag(label: &str, top: Vec2) -> impl Bundle {
     (
-        Name::new("Particle"),
-        Transform::from_translation(translation.extend(particle_config.local_z)),
-        Mesh2d(mesh),
-        MeshMaterial2d(material),
-        particle,
+        Name::new("Label Tag"),
+        Text2d::new(label),
+        TextFont::from_font_size(16.0),
+        Anchor::BottomCenter,
+        Transform::from_translation((top + Vec2::Y * LABEL_TAG_MARGIN).extend(LABEL_TAG_Z)),
     )
 }
 
@@ -235,20 +271,24 @@ pub fn obstacle_preview(
     transform: Transform,
     material: Handle<ColorMaterial>,
     mesh: Handle<Mesh>,
+    is_killer: bool,
 ) -> impl Bundle {
     (
         Name::new("Obstacle"),
         transform,
         Mesh2d(mesh),
         MeshMaterial2d(material),
+        Maybe(is_killer.then_some(KillerLook)),
     )
 }
 
-#[derive(Default, PartialEq, Eq)]
+#[derive(Default, PartialEq, Eq, Clone, Copy)]
 enum EditorMode {
     #[default]
     Place,
     Select,
+    /// Clicking plays the level with the player spawned at the cursor.
+    TestFromCursor,
 }
 
 #[derive(Default, PartialEq, Eq, Clone, Copy)]
@@ -258,32 +298,570 @@ enum Object {
     Obstacle,
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 enum PreviewIndex {
     Player,
     Particle(usize),
     Obstacle(usize),
 }
 
-#[derive(Resource, Default)]
+/// Switches to placing particles.
+const PLACE_PARTICLE_KEY: KeyCode = KeyCode::Digit1;
+/// Switches to placing obstacles.
+const PLACE_OBSTACLE_KEY: KeyCode = KeyCode::Digit2;
+/// Toggles between placing and selecting.
+const TOGGLE_MODE_KEY: KeyCode = KeyCode::Tab;
+
+/// Objects closer than this many world units are reported as duplicates by default.
+const DEFAULT_DUPLICATE_TOLERANCE: f32 = 1.0;
+/// A dragged object snaps to objects this many world units away by default.
+const DEFAULT_ALIGNMENT_TOLERANCE: f32 = 5.0;
+const DEFAULT_GRID_SIZE: f32 = 10.0;
+/// The fuse of particles the fuse is switched on for.
+const DEFAULT_FUSE: Duration = Duration::from_secs(3);
+
+#[derive(Resource)]
 pub struct EditorState {
     pub level: LevelData,
     mode: EditorMode,
     placement: Object,
     selected: Option<PreviewIndex>,
+    /// Every selected object, `selected` being the last one picked.
+    selection: Vec<PreviewIndex>,
     pub editing: bool,
     load_text: String,
+    /// Why the last loaded text couldn't be parsed, `None` if it could.
+    load_error: Option<String>,
+    /// The file loaded by [`EditorEvent::LoadFile`].
+    #[cfg(not(target_family = "wasm"))]
+    load_path: String,
+    duplicate_tolerance: f32,
+    /// The result of the last "Check level", `None` if it wasn't run yet.
+    warnings: Option<Vec<LevelWarning>>,
+    /// Subparticle indices leading from the selected particle to the one whose velocity handle
+    /// is shown, empty for the selected particle itself.
+    velocity_target: Vec<usize>,
+    dragging_velocity: bool,
+    /// Whether the current drag changed the level, it is only kept in the history once it does.
+    drag_moved: bool,
+    /// The level picked to be opened.
+    open_level: Option<Level>,
+    /// Overrides the player spawn for the next play, without changing the level.
+    test_spawn: Option<Vec2>,
+    /// The offset of the dragged object from the cursor, `None` if nothing is dragged.
+    object_drag: Option<Vec2>,
+    /// Where the last object was clicked, clicking there again selects the object below.
+    last_click: Option<Vec2>,
+    /// The object picked by the held mouse button and where, see [`EditorState::release_click`].
+    pending_click: Option<(PreviewIndex, Vec2)>,
+    alignment_snapping: bool,
+    alignment_tolerance: f32,
+    /// Snaps placed objects and edited positions to a grid of `grid_size`.
+    snap_enabled: bool,
+    grid_size: f32,
+    /// The guides the dragged object is aligned with, see [`alignment::Alignment`].
+    alignment_guides: (Option<f32>, Option<f32>),
+    /// The view when the level was played, restored when returning to the editor.
+    view_before_play: Option<EditorView>,
+    history: History,
+    /// Where the level was last saved to, or why it wasn't.
+    #[cfg(not(target_family = "wasm"))]
+    save_result: Option<SaveResult>,
+}
+
+/// What the designer was looking at, kept across playing the level.
+struct EditorView {
+    mode: EditorMode,
+    selected: Option<PreviewIndex>,
+    selection: Vec<PreviewIndex>,
+    velocity_target: Vec<usize>,
+    camera_translation: Vec3,
+    camera_scale: f32,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        Self {
+            level: LevelData::default(),
+            mode: EditorMode::default(),
+            placement: Object::default(),
+            selected: None,
+            selection: Vec::new(),
+            editing: false,
+            load_text: String::new(),
+            load_error: None,
+            #[cfg(not(target_family = "wasm"))]
+            load_path: String::new(),
+            duplicate_tolerance: DEFAULT_DUPLICATE_TOLERANCE,
+            warnings: None,
+            velocity_target: Vec::new(),
+            dragging_velocity: false,
+            drag_moved: false,
+            open_level: None,
+            test_spawn: None,
+            object_drag: None,
+            last_click: None,
+            pending_click: None,
+            alignment_snapping: true,
+            alignment_tolerance: DEFAULT_ALIGNMENT_TOLERANCE,
+            snap_enabled: false,
+            grid_size: DEFAULT_GRID_SIZE,
+            alignment_guides: (None, None),
+            view_before_play: None,
+            history: History::default(),
+            #[cfg(not(target_family = "wasm"))]
+            save_result: None,
+        }
+    }
+}
+
+impl EditorState {
+    fn apply_shortcut(&mut self, key: KeyCode) {
+        match key {
+            PLACE_PARTICLE_KEY => {
+                self.mode = EditorMode::Place;
+                self.placement = Object::Particle;
+            }
+            PLACE_OBSTACLE_KEY => {
+                self.mode = EditorMode::Place;
+                self.placement = Object::Obstacle;
+            }
+            TOGGLE_MODE_KEY => {
+                self.mode = match self.mode {
+                    EditorMode::Place => EditorMode::Select,
+                    EditorMode::Select | EditorMode::TestFromCursor => EditorMode::Place,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// The size of the grid positions snap to, `None` if snapping is off.
+    fn snap_grid(&self) -> Option<f32> {
+        self.snap_enabled.then_some(self.grid_size)
+    }
+
+    /// Keeps the level as it is, so the change about to be made can be undone.
+    fn record_history(&mut self) {
+        self.history.record(&self.level);
+    }
+
+    /// Keeps the level before the first change of a drag, so pressing an object without moving
+    /// it doesn't add a step that changes nothing.
+    fn record_drag_history(&mut self) {
+        if !self.drag_moved {
+            self.record_history();
+            self.drag_moved = true;
+        }
+    }
+
+    fn undo(&mut self) {
+        if self.history.undo(&mut self.level) {
+            self.deselect_missing();
+        }
+    }
+
+    fn redo(&mut self) {
+        if self.history.redo(&mut self.level) {
+            self.deselect_missing();
+        }
+    }
+
+    fn object_exists(&self, index: PreviewIndex) -> bool {
+        match index {
+            PreviewIndex::Player => true,
+            PreviewIndex::Particle(index) => index < self.level.particles.len(),
+            PreviewIndex::Obstacle(index) => index < self.level.obstacles.len(),
+        }
+    }
+
+    /// Drops the objects that are gone from the selection.
+    fn deselect_missing(&mut self) {
+        let selection = std::mem::take(&mut self.selection);
+        self.selection = selection
+            .into_iter()
+            .filter(|&index| self.object_exists(index))
+            .collect();
+
+        if self
+            .selected
+            .is_some_and(|selected| !self.object_exists(selected))
+        {
+            self.selected = self.selection.last().copied();
+            self.velocity_target.clear();
+        }
+    }
+
+    fn select(&mut self, selected: PreviewIndex) {
+        self.selected = Some(selected);
+        self.selection = vec![selected];
+        self.velocity_target.clear();
+    }
+
+    /// Adds the object to the selection, or removes it if it is already selected.
+    fn toggle_selection(&mut self, index: PreviewIndex) {
+        if let Some(position) = self.selection.iter().position(|&i| i == index) {
+            self.selection.remove(position);
+            self.selected = self.selection.last().copied();
+        } else {
+            self.selection.push(index);
+            self.selected = Some(index);
+        }
+        self.velocity_target.clear();
+    }
+
+    fn deselect(&mut self) {
+        self.selected = None;
+        self.selection.clear();
+        self.velocity_target.clear();
+    }
+
+    /// Deletes every selected object, the player spawn can't be deleted.
+    fn delete_selection(&mut self) {
+        self.record_history();
+
+        let mut particles = Vec::new();
+        let mut obstacles = Vec::new();
+        for index in &self.selection {
+            match *index {
+                PreviewIndex::Player => {}
+                PreviewIndex::Particle(i) => particles.push(i),
+                PreviewIndex::Obstacle(i) => obstacles.push(i),
+            }
+        }
+
+        // From the back, so the indices left to remove stay valid.
+        particles.sort_unstable_by(|a, b| b.cmp(a));
+        obstacles.sort_unstable_by(|a, b| b.cmp(a));
+        for i in particles {
+            self.level.particles.remove(i);
+        }
+        for i in obstacles {
+            self.level.obstacles.remove(i);
+        }
+
+        self.deselect();
+    }
+
+    /// The spawn position of the selected particle and the position of the velocity handle.
+    fn velocity_handle(&self) -> Option<(Vec2, Vec2)> {
+        let Some(PreviewIndex::Particle(index)) = self.selected else {
+            return None;
+        };
+        let particle_data = self.level.particles.get(index)?;
+
+        let mut particle = &particle_data.particle;
+        for &i in &self.velocity_target {
+            particle = particle.subparticles.get(i)?;
+        }
+
+        let origin = particle_data.spawn_position;
+        Some((
+            origin,
+            origin + particle.initial_velocity * VELOCITY_HANDLE_SCALE,
+        ))
+    }
+
+    fn object_position(&self, index: PreviewIndex) -> Option<Vec2> {
+        match index {
+            PreviewIndex::Player => Some(self.level.player_spawn),
+            PreviewIndex::Particle(i) => Some(self.level.particles.get(i)?.spawn_position),
+            PreviewIndex::Obstacle(i) => {
+                Some(self.level.obstacles.get(i)?.transform.translation.xy())
+            }
+        }
+    }
+
+    fn set_object_position(&mut self, index: PreviewIndex, position: Vec2) {
+        match index {
+            PreviewIndex::Player => self.level.player_spawn = position,
+            PreviewIndex::Particle(i) => {
+                if let Some(particle_data) = self.level.particles.get_mut(i) {
+                    particle_data.spawn_position = position;
+                }
+            }
+            PreviewIndex::Obstacle(i) => {
+                if let Some(obstacle_data) = self.level.obstacles.get_mut(i) {
+                    let translation = &mut obstacle_data.transform.translation;
+                    *translation = position.extend(translation.z);
+                }
+            }
+        }
+    }
+
+    /// Moves the dragged object, see [`Self::record_drag_history`].
+    fn drag_object_to(&mut self, index: PreviewIndex, position: Vec2) {
+        if self
+            .object_position(index)
+            .is_none_or(|current| current == position)
+        {
+            return;
+        }
+        self.record_drag_history();
+        self.set_object_position(index, position);
+    }
+
+    /// A copy of the selected particle or obstacle, `None` for the player spawn.
+    fn selected_object(&self) -> Option<ObjectData> {
+        match self.selected? {
+            PreviewIndex::Player => None,
+            PreviewIndex::Particle(index) => self
+                .level
+                .particles
+                .get(index)
+                .cloned()
+                .map(ObjectData::Particle),
+            PreviewIndex::Obstacle(index) => self
+                .level
+                .obstacles
+                .get(index)
+                .cloned()
+                .map(ObjectData::Obstacle),
+        }
+    }
+
+    /// Adds a copy of the selected object next to it and selects the copy.
+    fn duplicate_selected(&mut self) {
+        let Some(mut object) = self.selected_object() else {
+            return;
+        };
+        object.translate(DUPLICATE_OFFSET);
+        self.record_history();
+        paste_object(self, object);
+    }
+
+    /// Moves the selected particle or obstacle to the end of its list so it spawns last, or to
+    /// the start so it spawns first.
+    fn reorder_selected(&mut self, to_front: bool) {
+        fn reorder<T>(objects: &mut Vec<T>, index: usize, to_front: bool) -> usize {
+            let object = objects.remove(index);
+            if to_front {
+                objects.push(object);
+                objects.len() - 1
+            } else {
+                objects.insert(0, object);
+                0
+            }
+        }
+
+        let Some(selected) = self
+            .selected
+            .filter(|&index| index != PreviewIndex::Player && self.object_exists(index))
+        else {
+            return;
+        };
+        self.record_history();
+
+        let reordered = match selected {
+            PreviewIndex::Player => return,
+            PreviewIndex::Particle(index) => {
+                PreviewIndex::Particle(reorder(&mut self.level.particles, index, to_front))
+            }
+            PreviewIndex::Obstacle(index) => {
+                PreviewIndex::Obstacle(reorder(&mut self.level.obstacles, index, to_front))
+            }
+        };
+        self.select(reordered);
+    }
+
+    /// Every object under `point`, the ones spawned last first.
+    fn objects_at(&self, point: Vec2, player_radius: f32) -> Vec<PreviewIndex> {
+        let particles = (0..self.level.particles.len())
+            .rev()
+            .map(PreviewIndex::Particle);
+        let obstacles = (0..self.level.obstacles.len())
+            .rev()
+            .map(PreviewIndex::Obstacle);
+
+        std::iter::once(PreviewIndex::Player)
+            .chain(particles)
+            .chain(obstacles)
+            .filter(|&index| self.object_contains(index, point, player_radius))
+            .collect()
+    }
+
+    /// Selects the `picked` object, or the one below the selected object when clicking the same
+    /// spot again, cycling through everything under the cursor.
+    fn click_select(&mut self, picked: PreviewIndex, point: Vec2, player_radius: f32) {
+        let same_spot = self
+            .last_click
+            .is_some_and(|last| last.distance(point) <= CYCLE_CLICK_DISTANCE);
+        self.last_click = Some(point);
+
+        let candidates = self.objects_at(point, player_radius);
+        let below = self
+            .selected
+            .filter(|_| same_spot)
+            .and_then(|selected| candidates.iter().position(|&index| index == selected))
+            .map(|position| candidates[(position + 1) % candidates.len()]);
+        self.select(below.unwrap_or(picked));
+    }
+
+    /// Selects the `picked` object when pressing the mouse button, keeping the selected object if
+    /// it is under the cursor so pressing it again drags it rather than the object below.
+    fn press_select(&mut self, picked: PreviewIndex, point: Vec2, player_radius: f32) {
+        self.pending_click = Some((picked, point));
+        self.drag_moved = false;
+        if self
+            .selected
+            .is_some_and(|selected| self.object_contains(selected, point, player_radius))
+        {
+            return;
+        }
+        self.select(picked);
+        // Releasing this press mustn't cycle past the object it just selected.
+        self.last_click = None;
+    }
+
+    /// Finishes the click once the mouse button is released, see [`Self::click_select`]. A press
+    /// that dragged something isn't a click.
+    fn release_click(&mut self, player_radius: f32) {
+        let Some((picked, point)) = self.pending_click.take() else {
+            return;
+        };
+        if self.drag_moved {
+            self.last_click = None;
+        } else {
+            self.click_select(picked, point, player_radius);
+        }
+    }
+
+    /// Moves the selected objects by `offset`, onto the grid if snapping is on.
+    fn nudge_selection(&mut self, offset: Vec2) {
+        if self.selection.is_empty() {
+            return;
+        }
+        self.record_history();
+
+        for index in self.selection.clone() {
+            let Some(position) = self.object_position(index) else {
+                continue;
+            };
+            let mut target = position + offset;
+            if let Some(grid_size) = self.snap_grid() {
+                target = snap_to_grid(target, grid_size);
+            }
+            self.set_object_position(index, target);
+        }
+    }
+
+    /// Whether `point` is on the object, to start dragging it.
+    fn object_contains(&self, index: PreviewIndex, point: Vec2, player_radius: f32) -> bool {
+        match index {
+            PreviewIndex::Player => self.level.player_spawn.distance(point) <= player_radius,
+            PreviewIndex::Particle(i) => self.level.particles.get(i).is_some_and(|particle_data| {
+                particle_data.spawn_position.distance(point) <= particle_data.particle.radius
+            }),
+            PreviewIndex::Obstacle(i) => self
+                .level
+                .obstacles
+                .get(i)
+                .is_some_and(|obstacle_data| obstacle_data.contains(point)),
+        }
+    }
+
+    /// The alignment keys of every object, with the index of the object.
+    fn alignment_keys(&self, player_radius: f32) -> Vec<(PreviewIndex, AlignmentKeys)> {
+        let player = (
+            PreviewIndex::Player,
+            AlignmentKeys::circle(self.level.player_spawn, player_radius),
+        );
+        let particles = self
+            .level
+            .particles
+            .iter()
+            .enumerate()
+            .map(|(i, particle_data)| {
+                (
+                    PreviewIndex::Particle(i),
+                    AlignmentKeys::circle(
+                        particle_data.spawn_position,
+                        particle_data.particle.radius,
+                    ),
+                )
+            });
+        let obstacles = self
+            .level
+            .obstacles
+            .iter()
+            .enumerate()
+            .map(|(i, obstacle_data)| {
+                (
+                    PreviewIndex::Obstacle(i),
+                    AlignmentKeys::polygon(
+                        obstacle_data.transform.translation.xy(),
+                        &obstacle_data.corners(),
+                    ),
+                )
+            });
+
+        std::iter::once(player)
+            .chain(particles)
+            .chain(obstacles)
+            .collect()
+    }
+
+    fn velocity_target_mut(&mut self) -> Option<&mut Particle> {
+        let Some(PreviewIndex::Particle(index)) = self.selected else {
+            return None;
+        };
+
+        let mut particle = &mut self.level.particles.get_mut(index)?.particle;
+        for &i in &self.velocity_target {
+            particle = particle.subparticles.get_mut(i)?;
+        }
+
+        Some(particle)
+    }
+
+    /// Sets the initial velocity of the particle whose velocity handle is dragged, see
+    /// [`Self::record_drag_history`].
+    fn drag_velocity_to(&mut self, velocity: Vec2) {
+        if self
+            .velocity_target_mut()
+            .is_none_or(|particle| particle.initial_velocity == velocity)
+        {
+            return;
+        }
+        self.record_drag_history();
+        if let Some(particle) = self.velocity_target_mut() {
+            particle.initial_velocity = velocity;
+        }
+    }
 }
 
 #[derive(Event, PartialEq, Eq)]
 enum EditorEvent {
     Exit,
     Print,
+    #[cfg(not(target_family = "wasm"))]
+    Save,
+    /// Saves over the file of a level with the same name, see [`SaveResult::Exists`].
+    #[cfg(not(target_family = "wasm"))]
+    Overwrite,
+    CopyObject,
     Load,
+    #[cfg(not(target_family = "wasm"))]
+    LoadFile,
+    Open,
     Clear,
+    MirrorX,
+    MirrorY,
+    Rotate,
+    Check,
+    Simulate,
+    StopSimulation,
     Play,
 }
 
+/// Present while the editor runs the particle physics on a copy of the level.
+#[derive(Resource)]
+pub struct EditorSimulation;
+
+/// The root of the entities spawned for an [`EditorSimulation`].
+#[derive(Component)]
+struct LevelSimulation;
+
 fn handle_editor_event_exit(
     mut events: EventReader<EditorEvent>,
     mut editor_state: ResMut<EditorState>,
@@ -314,19 +892,275 @@ fn handle_editor_event_print(
     }
 }
 
+/// Where levels are saved from the editor, in the asset root rather than the working directory.
+#[cfg(not(target_family = "wasm"))]
+fn saved_levels_dir() -> PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path().join("assets/levels/custom")
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn handle_editor_event_save(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    for event in events.read() {
+        // Saving again replaces the file saved to last without asking.
+        let overwrite = match (event, &editor_state.save_result) {
+            (EditorEvent::Save, Some(SaveResult::Saved(path)))
+            | (EditorEvent::Overwrite, Some(SaveResult::Exists(path))) => Some(path.clone()),
+            (EditorEvent::Save | EditorEvent::Overwrite, _) => None,
+            _ => continue,
+        };
+        editor_state.save_result = Some(save_level(
+            &saved_levels_dir(),
+            &editor_state.level,
+            overwrite.as_deref(),
+        ));
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+enum SaveResult {
+    Saved(PathBuf),
+    /// A level with the same name is saved at the path, it is only replaced once confirmed.
+    Exists(PathBuf),
+    Failed(String),
+}
+
+/// Writes the level to `dir`, named after it, see [`level_file_name`].
+///
+/// Different names can map to the same file, a file holding another level is never replaced. One
+/// holding a level with the same name is only replaced if it is `overwrite`.
+#[cfg(not(target_family = "wasm"))]
+fn save_level(dir: &Path, level: &LevelData, overwrite: Option<&Path>) -> SaveResult {
+    let Some(file_name) = level_file_name(&level.name) else {
+        return SaveResult::Failed(String::from("The level needs a name to be saved."));
+    };
+    let path = dir.join(format!("{file_name}.ron"));
+
+    if let Ok(bytes) = std::fs::read(&path) {
+        match LevelData::from_ron(&bytes) {
+            Ok(saved) if saved.name == level.name => {
+                if overwrite != Some(path.as_path()) {
+                    return SaveResult::Exists(path);
+                }
+            }
+            Ok(saved) => {
+                return SaveResult::Failed(format!(
+                    "`{}` is the level \"{}\", rename this level to save it.",
+                    path.display(),
+                    saved.name
+                ));
+            }
+            Err(_) => {
+                return SaveResult::Failed(format!(
+                    "`{}` isn't a level, rename this level to save it.",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    let ron = match ron::ser::to_string_pretty(level, ron::ser::PrettyConfig::default()) {
+        Ok(ron) => ron,
+        Err(err) => return SaveResult::Failed(err.to_string()),
+    };
+    if let Err(err) = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&path, ron)) {
+        return SaveResult::Failed(format!("Failed to save to `{}`: {err}", path.display()));
+    }
+
+    info!("Saved level to `{}`", path.display());
+    SaveResult::Saved(path)
+}
+
+/// The name of the file a level is saved to, lowercase with underscores instead of spaces.
+///
+/// Characters that could leave the directory are dropped, `None` if nothing is left.
+#[cfg(not(target_family = "wasm"))]
+fn level_file_name(name: &str) -> Option<String> {
+    let file_name: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .filter(|&c| c.is_alphanumeric() || c == '_' || c == '-')
+        .collect();
+
+    (!file_name.is_empty()).then_some(file_name)
+}
+
+fn handle_editor_event_copy_object(
+    mut events: EventReader<EditorEvent>,
+    editor_state: Res<EditorState>,
+    mut contexts: EguiContexts,
+) {
+    for event in events.read() {
+        if *event != EditorEvent::CopyObject {
+            continue;
+        }
+
+        if let Some(object) = editor_state.selected_object() {
+            let string: String =
+                ron::ser::to_string_pretty(&object, ron::ser::PrettyConfig::default()).unwrap();
+
+            let ctx = contexts.ctx_mut();
+            ctx.copy_text(string);
+        }
+    }
+}
+
+/// How far a pasted object is moved, so it doesn't hide the one it was copied from.
+const PASTE_OFFSET: Vec2 = Vec2::new(20.0, -20.0);
+/// How far duplicates are from the duplicated object.
+const DUPLICATE_OFFSET: Vec2 = Vec2::new(20.0, 20.0);
+/// Clicks this many world units apart count as clicking the same spot, to select what is below.
+const CYCLE_CLICK_DISTANCE: f32 = 2.0;
+
 fn handle_editor_event_load(
     mut events: EventReader<EditorEvent>,
     mut editor_state: ResMut<EditorState>,
 ) {
     for event in events.read() {
-        if *event == EditorEvent::Load {
-            if let Ok(level_data) = ron::from_str::<LevelData>(&editor_state.load_text) {
-                editor_state.level = level_data;
+        if *event != EditorEvent::Load {
+            continue;
+        }
+
+        let text = editor_state.load_text.clone();
+        load_ron(&mut editor_state, &text);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn handle_editor_event_load_file(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    for event in events.read() {
+        if *event != EditorEvent::LoadFile {
+            continue;
+        }
+
+        match std::fs::read_to_string(&editor_state.load_path) {
+            Ok(text) => load_ron(&mut editor_state, &text),
+            Err(err) => {
+                editor_state.load_error = Some(format!(
+                    "Failed to read `{}`: {err}",
+                    editor_state.load_path
+                ));
             }
         }
     }
 }
 
+/// Loads text pasted while no text field is focused.
+fn paste_into_editor(mut contexts: EguiContexts, mut editor_state: ResMut<EditorState>) {
+    let ctx = contexts.ctx_mut();
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+
+    let pasted = ctx.input(|input| {
+        input.events.iter().find_map(|event| match event {
+            egui::Event::Paste(text) => Some(text.clone()),
+            _ => None,
+        })
+    });
+    if let Some(text) = pasted {
+        load_ron(&mut editor_state, &text);
+    }
+}
+
+/// Replaces the level with the level in `text`, or pastes the object in it.
+///
+/// The level is kept if neither can be parsed, the error of parsing the level is shown instead.
+fn load_ron(editor_state: &mut EditorState, text: &str) {
+    let level_error = match LevelData::from_ron(text.as_bytes()) {
+        Ok(level_data) => {
+            editor_state.record_history();
+            editor_state.level = level_data;
+            editor_state.deselect();
+            editor_state.load_error = None;
+            return;
+        }
+        Err(err) => err,
+    };
+
+    if let Ok(mut object) = ron::de::from_str::<ObjectData>(text) {
+        object.translate(PASTE_OFFSET);
+        editor_state.record_history();
+        paste_object(editor_state, object);
+        editor_state.load_error = None;
+        return;
+    }
+
+    editor_state.load_error = Some(level_error.to_string());
+}
+
+/// Appends a single object to the level and selects it.
+fn paste_object(editor_state: &mut EditorState, object: ObjectData) {
+    let selected = match object {
+        ObjectData::Particle(particle_data) => {
+            editor_state.level.particles.push(particle_data);
+            PreviewIndex::Particle(editor_state.level.particles.len() - 1)
+        }
+        ObjectData::Obstacle(obstacle_data) => {
+            editor_state.level.obstacles.push(obstacle_data);
+            PreviewIndex::Obstacle(editor_state.level.obstacles.len() - 1)
+        }
+    };
+
+    editor_state.mode = EditorMode::Select;
+    editor_state.select(selected);
+}
+
+/// Replaces the level in the editor.
+#[derive(Event)]
+pub struct LoadLevelIntoEditor(pub LevelData);
+
+fn load_level_into_editor(
+    mut events: EventReader<LoadLevelIntoEditor>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    if let Some(LoadLevelIntoEditor(level_data)) = events.read().last() {
+        editor_state.record_history();
+        editor_state.level = level_data.clone();
+        editor_state.deselect();
+        editor_state.warnings = None;
+    }
+}
+
+fn handle_editor_event_open(
+    mut events: EventReader<EditorEvent>,
+    editor_state: Res<EditorState>,
+    level_assets: Option<Res<LevelAssets>>,
+    levels: Res<Assets<LevelData>>,
+    mut load_events: EventWriter<LoadLevelIntoEditor>,
+) {
+    for event in events.read() {
+        if *event != EditorEvent::Open {
+            continue;
+        }
+
+        let (Some(level), Some(level_assets)) = (&editor_state.open_level, &level_assets) else {
+            continue;
+        };
+        let handle = match level {
+            Level::Default(id) => level_assets.default.get(*id),
+            Level::Custom(name) => level_assets.custom.get(name),
+        };
+        let Some(mut level_data) = handle.and_then(|handle| levels.get(handle)).cloned() else {
+            continue;
+        };
+
+        // The edited level is a new custom level, it shouldn't pass for the bundled one.
+        if let Level::Default(_) = level {
+            level_data.name = format!("{} (edited)", level_data.name);
+        }
+
+        load_events.write(LoadLevelIntoEditor(level_data));
+    }
+}
+
 fn handle_editor_event_clear(
     mut events: EventReader<EditorEvent>,
     mut editor_state: ResMut<EditorState>,
@@ -334,24 +1168,171 @@ fn handle_editor_event_clear(
 ) {
     for event in events.read() {
         if *event == EditorEvent::Clear {
+            editor_state.record_history();
             editor_state.level = LevelData::default();
             commands.trigger(SpawnLevelPreview);
         }
     }
 }
 
-fn handle_editor_event_play(
+fn handle_editor_event_mirror(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    for event in events.read() {
+        let scale = match *event {
+            EditorEvent::MirrorX => vec2(-1.0, 1.0),
+            EditorEvent::MirrorY => vec2(1.0, -1.0),
+            _ => continue,
+        };
+        editor_state.record_history();
+        editor_state.level.mirror(scale);
+    }
+}
+
+fn handle_editor_event_rotate(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    for event in events.read() {
+        if *event == EditorEvent::Rotate {
+            editor_state.record_history();
+            editor_state.level.rotate(Rot2::degrees(90.0));
+        }
+    }
+}
+
+fn handle_editor_event_check(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+    letterboxing: Res<Letterboxing>,
+) {
+    for event in events.read() {
+        if *event == EditorEvent::Check {
+            let bounds = vec2(
+                letterboxing.projection_size.width,
+                letterboxing.projection_size.height,
+            );
+            let warnings = editor_state
+                .level
+                .validate(bounds, editor_state.duplicate_tolerance);
+            editor_state.warnings = Some(warnings);
+        }
+    }
+}
+
+fn handle_editor_event_simulate(
     mut events: EventReader<EditorEvent>,
     editor_state: Res<EditorState>,
+    level_preview_query: Query<Entity, With<LevelPreview>>,
+    level_simulation_query: Query<Entity, With<LevelSimulation>>,
+    letterboxing: Res<Letterboxing>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut pop_sounds: ResMut<PopSounds>,
+    mut commands: Commands,
+) {
+    for event in events.read() {
+        match *event {
+            EditorEvent::Simulate => {
+                commands.insert_resource(EditorSimulation);
+                pop_sounds.muted = !editor_state.level.pop_sounds;
+
+                for level_preview in level_preview_query.iter() {
+                    commands.entity(level_preview).despawn();
+                }
+
+                // Only a copy is spawned, the edited level stays untouched.
+                let level = &editor_state.level;
+                let level_simulation = commands
+                    .spawn((
+                        Name::new("Level Simulation"),
+                        LevelSimulation,
+                        Transform::default(),
+                        Visibility::default(),
+                        StateScoped(Screen::Editor),
+                        children![level_bounds(
+                            level.bounds(vec2(
+                                letterboxing.projection_size.width,
+                                letterboxing.projection_size.height,
+                            )),
+                            level.wall_restitution,
+                        )],
+                    ))
+                    .id();
+
+                for obstacle_data in &level.obstacles {
+                    let material = materials.add(obstacle_data.display_color());
+                    let mesh = meshes.add(obstacle_data.mesh());
+
+                    commands.spawn((
+                        obstacle(
+                            obstacle_data.transform,
+                            material,
+                            mesh,
+                            obstacle_data.collider(),
+                            obstacle_data.is_killer,
+                        ),
+                        Maybe(obstacle_data.trigger.as_ref().map(trigger_zone)),
+                        ChildOf(level_simulation),
+                    ));
+                }
+
+                for particle_data in &level.particles {
+                    commands.trigger(SpawnParticle {
+                        translation: particle_data.spawn_position,
+                        particle: particle_data.particle.clone(),
+                        spawn_with_invincible: false,
+                        show_arrows: level.show_arrows,
+                        parent: Some(level_simulation),
+                    });
+                }
+            }
+            EditorEvent::StopSimulation => {
+                commands.remove_resource::<EditorSimulation>();
+
+                for level_simulation in level_simulation_query.iter() {
+                    commands.entity(level_simulation).despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn handle_editor_event_play(
+    mut events: EventReader<EditorEvent>,
+    mut editor_state: ResMut<EditorState>,
+    camera_query: Query<(&Transform, &Projection), With<GameplayCamera>>,
     mut next_screen: ResMut<NextState<Screen>>,
     mut selected_level: ResMut<SelectedLevel>,
     mut commands: Commands,
 ) {
     if let Some(event) = events.read().next() {
         if *event == EditorEvent::Play {
+            let mut data = editor_state.level.clone();
+            if let Some(test_spawn) = editor_state.test_spawn.take() {
+                data.player_spawn = test_spawn;
+            }
+
+            let (camera_translation, camera_scale) = camera_query
+                .single()
+                .map_or((Vec3::ZERO, 1.0), |(transform, projection)| {
+                    (transform.translation, camera_scale(projection))
+                });
+            editor_state.view_before_play = Some(EditorView {
+                mode: editor_state.mode,
+                selected: editor_state.selected,
+                selection: editor_state.selection.clone(),
+                velocity_target: editor_state.velocity_target.clone(),
+                camera_translation,
+                camera_scale,
+            });
+
             commands.trigger(SpawnRawLevel {
-                data: editor_state.level.clone(),
+                data,
                 level: None,
+                source: PlaySource::EditorTest,
             });
             selected_level.0 = None;
             next_screen.set(Screen::Gameplay);
@@ -359,7 +1340,7 @@ fn handle_editor_event_play(
     }
 }
 
-fn vec2_input_ui(ui: &mut Ui, vec2: &mut Vec2) -> InnerResponse<()> {
+pub(super) fn vec2_input_ui(ui: &mut Ui, vec2: &mut Vec2) -> InnerResponse<()> {
     ui.horizontal(|ui| {
         ui.label("x:");
         ui.add(egui::DragValue::new(&mut vec2.x));
@@ -368,6 +1349,165 @@ fn vec2_input_ui(ui: &mut Ui, vec2: &mut Vec2) -> InnerResponse<()> {
     })
 }
 
+/// Like [`vec2_input_ui`], snapping the position to the grid when it is edited.
+fn position_input_ui(ui: &mut Ui, position: &mut Vec2, grid_size: Option<f32>) {
+    let before = *position;
+    vec2_input_ui(ui, position);
+
+    if let Some(grid_size) = grid_size.filter(|_| *position != before) {
+        *position = snap_to_grid(*position, grid_size);
+    }
+}
+
+/// Rounds `position` to the nearest multiple of `grid_size`, a grid size of 0 doesn't snap.
+fn snap_to_grid(position: Vec2, grid_size: f32) -> Vec2 {
+    if grid_size <= 0.0 {
+        return position;
+    }
+
+    (position / grid_size).round() * grid_size
+}
+
+/// The fuse of a particle, unchecked for particles that only split when hit.
+fn fuse_input_ui(ui: &mut Ui, fuse: &mut Option<Duration>) {
+    ui.horizontal(|ui| {
+        let mut enabled = fuse.is_some();
+        ui.checkbox(&mut enabled, "");
+        if enabled != fuse.is_some() {
+            *fuse = enabled.then_some(DEFAULT_FUSE);
+        }
+
+        if let Some(duration) = fuse {
+            let mut secs = duration.as_secs_f32();
+            ui.add(
+                egui::DragValue::new(&mut secs)
+                    .range(0.1..=60.0)
+                    .speed(0.1)
+                    .suffix(" s"),
+            );
+            *duration = Duration::from_secs_f32(secs);
+        }
+    });
+}
+
+fn objective_input_ui(ui: &mut Ui, objective: &mut Objective) {
+    let name = |objective: &Objective| match objective {
+        Objective::ClearParticles => "Clear Particles",
+        Objective::DestroyKillers => "Destroy Killers",
+        Objective::Survive(_) => "Survive",
+        Objective::FreePlay => "Free Play",
+    };
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("objective")
+            .selected_text(name(objective))
+            .show_ui(ui, |ui| {
+                for option in [
+                    Objective::ClearParticles,
+                    Objective::DestroyKillers,
+                    Objective::Survive(Duration::from_secs(30)),
+                    Objective::FreePlay,
+                ] {
+                    let selected = name(objective) == name(&option);
+                    if ui.selectable_label(selected, name(&option)).clicked() && !selected {
+                        *objective = option;
+                    }
+                }
+            });
+
+        if let Objective::Survive(duration) = objective {
+            let mut secs = duration.as_secs_f32();
+            ui.add(
+                egui::DragValue::new(&mut secs)
+                    .range(1.0..=600.0)
+                    .suffix(" s"),
+            );
+            *duration = Duration::from_secs_f32(secs);
+        }
+    });
+}
+
+fn obstacle_shape_input_ui(ui: &mut Ui, obstacle: &mut ObstacleData) {
+    let name = |shape: &ObstacleShape| match shape {
+        ObstacleShape::Rectangle => "Rectangle",
+        ObstacleShape::Circle { .. } => "Circle",
+    };
+    // A new circle fits the width of the rectangle it replaces.
+    let circle = ObstacleShape::Circle {
+        radius: obstacle.width / 2.0,
+    };
+
+    egui::ComboBox::from_id_salt("obstacle_shape")
+        .selected_text(name(&obstacle.shape))
+        .show_ui(ui, |ui| {
+            for option in [ObstacleShape::Rectangle, circle] {
+                let selected = name(&obstacle.shape) == name(&option);
+                if ui.selectable_label(selected, name(&option)).clicked() && !selected {
+                    obstacle.shape = option;
+                }
+            }
+        });
+}
+
+fn shape_input_ui(ui: &mut Ui, path: &[usize], shape: &mut ParticleShape) {
+    let polygon = ParticleShape::Polygon { sides: 6 };
+    let rounded_rectangle = ParticleShape::RoundedRectangle {
+        half_height: 10.0,
+        corner_radius: 5.0,
+    };
+    let name = |shape: &ParticleShape| match shape {
+        ParticleShape::Ball => "Ball",
+        ParticleShape::Polygon { .. } => "Polygon",
+        ParticleShape::RoundedRectangle { .. } => "Rounded Rectangle",
+    };
+
+    ui.vertical(|ui| {
+        egui::ComboBox::from_id_salt(("particle_shape", path))
+            .selected_text(name(shape))
+            .show_ui(ui, |ui| {
+                for option in [ParticleShape::Ball, polygon, rounded_rectangle] {
+                    // Switching keeps the parameters of the current shape.
+                    let selected = name(shape) == name(&option);
+                    if ui.selectable_label(selected, name(&option)).clicked() && !selected {
+                        *shape = option;
+                    }
+                }
+            });
+
+        match shape {
+            ParticleShape::Ball => {}
+            ParticleShape::Polygon { sides } => {
+                ui.horizontal(|ui| {
+                    ui.label("Sides:");
+                    ui.add(egui::DragValue::new(sides).range(3..=12));
+                });
+            }
+            ParticleShape::RoundedRectangle {
+                half_height,
+                corner_radius,
+            } => {
+                ui.horizontal(|ui| {
+                    ui.label("Half Height:");
+                    ui.add(egui::DragValue::new(half_height).range(1.0..=f32::MAX));
+                    ui.label("Corners:");
+                    ui.add(egui::DragValue::new(corner_radius).range(0.0..=f32::MAX));
+                });
+            }
+        }
+    });
+}
+
+/// Edits an optional label, an empty text means no label.
+fn label_input_ui(ui: &mut Ui, label: &mut Option<String>) {
+    let mut text = label.clone().unwrap_or_default();
+    ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .hint_text("None")
+            .desired_width(150.0),
+    );
+    *label = (!text.is_empty()).then_some(text);
+}
+
 fn vec2_angle_magnitude_input_ui(ui: &mut Ui, vec2: &mut Vec2) {
     let mut magnitude = vec2.length();
     let mut angle = vec2.y.atan2(vec2.x).to_degrees();
@@ -386,11 +1526,13 @@ fn vec2_angle_magnitude_input_ui(ui: &mut Ui, vec2: &mut Vec2) {
     *vec2 = Vec2::new(angle.cos(), angle.sin()) * magnitude;
 }
 
-fn particle_ui(
+pub(super) fn particle_ui(
     ui: &mut Ui,
     superparticle: bool,
     id: usize,
+    path: &[usize],
     particle: &mut Particle,
+    velocity_target: &mut Vec<usize>,
 ) -> Option<usize> {
     let mut to_delete = None;
 
@@ -424,16 +1566,45 @@ fn particle_ui(
                     ui.add(egui::DragValue::new(&mut particle.radius));
                     ui.end_row();
 
+                    ui.label("Shape:");
+                    shape_input_ui(ui, path, &mut particle.shape);
+                    ui.end_row();
+
                     ui.label("Velocity:");
                     vec2_angle_magnitude_input_ui(ui, &mut particle.initial_velocity);
                     ui.end_row();
 
-                    let mut is_killer = particle.kind == ParticleKind::Killer;
-                    ui.checkbox(&mut is_killer, "Is Killer");
-                    if is_killer {
-                        particle.kind = ParticleKind::Killer;
+                    ui.label("Angular Velocity:");
+                    ui.add(
+                        egui::DragValue::new(&mut particle.initial_angvel)
+                            .speed(0.1)
+                            .suffix(" rad/s"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Fuse:");
+                    fuse_input_ui(ui, &mut particle.fuse);
+                    ui.end_row();
+
+                    ui.label("Velocity Handle:");
+                    if ui
+                        .selectable_label(velocity_target == path, "Show")
+                        .clicked()
+                    {
+                        *velocity_target = path.to_vec();
+                    }
+                    ui.end_row();
+
+                    if let ParticleKind::Custom(name) = &particle.kind {
+                        ui.label(format!("Kind: {name}"));
                     } else {
-                        particle.kind = ParticleKind::Normal;
+                        let mut is_killer = particle.kind == ParticleKind::Killer;
+                        ui.checkbox(&mut is_killer, "Is Killer");
+                        if is_killer {
+                            particle.kind = ParticleKind::Killer;
+                        } else {
+                            particle.kind = ParticleKind::Normal;
+                        }
                     }
                     ui.end_row();
                 });
@@ -444,11 +1615,14 @@ fn particle_ui(
                 .show(ui, |ui| {
                     let mut deleted = None;
                     for (i, subparticle) in particle.subparticles.iter_mut().enumerate() {
-                        deleted = particle_ui(ui, false, i, subparticle);
+                        let path = [path, &[i]].concat();
+                        deleted = particle_ui(ui, false, i, &path, subparticle, velocity_target);
                     }
 
                     if let Some(deleted) = deleted {
                         particle.subparticles.remove(deleted);
+                        // The indices after the deleted subparticle shifted.
+                        velocity_target.clear();
                     }
                 });
 
@@ -462,9 +1636,40 @@ fn particle_ui(
 
 fn editor_ui(
     mut contexts: EguiContexts,
-    mut state: ResMut<EditorState>,
+    state: ResMut<EditorState>,
+    level_assets: Option<Res<LevelAssets>>,
+    levels: Res<Assets<LevelData>>,
+    simulation: Option<Res<EditorSimulation>>,
+    letterboxing: Res<Letterboxing>,
     mut events: EventWriter<EditorEvent>,
 ) {
+    if simulation.is_some() {
+        egui::Window::new("Editor")
+            .default_pos([10.0, 10.0])
+            .show(contexts.ctx_mut(), |ui| {
+                ui.label("Simulating, the level can't be edited.");
+                if ui.button("Stop Simulation").clicked() {
+                    events.write(EditorEvent::StopSimulation);
+                }
+            });
+        return;
+    }
+    let state = state.into_inner();
+
+    // Custom levels are looked up by name, so a copied level shouldn't reuse a taken one.
+    let name_taken = level_assets
+        .as_ref()
+        .is_some_and(|level_assets| level_assets.custom.contains_key(&state.level.name));
+
+    let openable_levels: Vec<_> = level_assets
+        .iter()
+        .flat_map(|level_assets| level_assets.all_levels(&levels))
+        .map(|info| {
+            let name = info.level.fallback_name();
+            (info.level, name)
+        })
+        .collect();
+
     egui::Window::new("Editor")
         .default_pos([10.0, 10.0])
         .collapsible(true)
@@ -477,22 +1682,122 @@ fn editor_ui(
                         events.write(EditorEvent::Play);
                     }
 
+                    if ui.button("Simulate").clicked() {
+                        events.write(EditorEvent::Simulate);
+                        state.deselect();
+                    }
+
                     if ui.button("Copy to Clipboard").clicked() {
                         events.write(EditorEvent::Print);
                     }
 
+                    #[cfg(not(target_family = "wasm"))]
+                    if ui.button("Save").clicked() {
+                        events.write(EditorEvent::Save);
+                    }
+
+                    let object_selected = matches!(
+                        state.selected,
+                        Some(PreviewIndex::Particle(_) | PreviewIndex::Obstacle(_))
+                    );
+                    if ui
+                        .add_enabled(object_selected, egui::Button::new("Copy Object"))
+                        .clicked()
+                    {
+                        events.write(EditorEvent::CopyObject);
+                    }
+
                     if ui.button("Clear").clicked() {
                         events.write(EditorEvent::Clear);
-                        state.selected = None;
+                        state.deselect();
                         return;
                     }
                 });
 
+                #[cfg(not(target_family = "wasm"))]
+                match &state.save_result {
+                    Some(SaveResult::Saved(path)) => {
+                        ui.label(format!("Saved to {}", path.display()));
+                    }
+                    Some(SaveResult::Exists(path)) => {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                format!("`{}` already exists.", path.display()),
+                            );
+                            if ui.button("Overwrite").clicked() {
+                                events.write(EditorEvent::Overwrite);
+                            }
+                        });
+                    }
+                    Some(SaveResult::Failed(err)) => {
+                        ui.colored_label(egui::Color32::RED, err);
+                    }
+                    None => {}
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Mirror X").clicked() {
+                        events.write(EditorEvent::MirrorX);
+                    }
+
+                    if ui.button("Mirror Y").clicked() {
+                        events.write(EditorEvent::MirrorY);
+                    }
+
+                    if ui.button("Rotate 90°").clicked() {
+                        events.write(EditorEvent::Rotate);
+                    }
+                });
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.button("Check level").clicked() {
+                        events.write(EditorEvent::Check);
+                    }
+
+                    ui.label("Tolerance:");
+                    ui.add(
+                        egui::DragValue::new(&mut state.duplicate_tolerance)
+                            .range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    );
+                });
+
+                warnings_ui(ui, state);
+
                 ui.separator();
 
+                ui.horizontal(|ui| {
+                    let selected_text = openable_levels
+                        .iter()
+                        .find(|(level, _)| Some(level) == state.open_level.as_ref())
+                        .map_or("Pick a level", |(_, name)| name.as_str());
+
+                    egui::ComboBox::from_id_salt("open_level")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            for (level, name) in &openable_levels {
+                                ui.selectable_value(
+                                    &mut state.open_level,
+                                    Some(level.clone()),
+                                    name,
+                                );
+                            }
+                        });
+
+                    if ui
+                        .add_enabled(state.open_level.is_some(), egui::Button::new("Open"))
+                        .clicked()
+                    {
+                        events.write(EditorEvent::Open);
+                        state.deselect();
+                    }
+                });
+
                 if ui.button("Load").clicked() {
                     events.write(EditorEvent::Load);
-                    state.selected = None;
                     return;
                 }
 
@@ -503,10 +1808,25 @@ fn editor_ui(
                             egui::TextEdit::multiline(&mut state.load_text)
                                 .frame(true)
                                 .desired_rows(10)
-                                .hint_text("Enter level here..."),
+                                .hint_text("Enter a level or a copied object here..."),
                         );
                     });
 
+                #[cfg(not(target_family = "wasm"))]
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.load_path)
+                            .hint_text(saved_levels_dir().join("level.ron").display().to_string()),
+                    );
+                    if ui.button("Load File").clicked() {
+                        events.write(EditorEvent::LoadFile);
+                    }
+                });
+
+                if let Some(err) = &state.load_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+
                 ui.separator();
 
                 if ui.button("Quit to title").clicked() {
@@ -525,33 +1845,150 @@ fn editor_ui(
                         );
                         ui.end_row();
 
-                        let author = &mut String::new();
-                        if let Some(a) = state.level.author.clone() {
-                            *author = a;
-                        }
+                        let author = &mut String::new();
+                        if let Some(a) = state.level.author.clone() {
+                            *author = a;
+                        }
+
+                        ui.label("Author:");
+                        ui.add(
+                            egui::TextEdit::singleline(author)
+                                .hint_text("None")
+                                .desired_width(150.0),
+                        );
+
+                        state.level.author = (!author.is_empty()).then_some(author.clone());
+                        ui.end_row();
+
+                        ui.label("Arrows:");
+                        ui.checkbox(&mut state.level.show_arrows, "Show in gameplay");
+                        ui.end_row();
+
+                        ui.label("Pop Sounds:");
+                        ui.checkbox(&mut state.level.pop_sounds, "Play when splitting");
+                        ui.end_row();
+
+                        let screen = vec2(
+                            letterboxing.projection_size.width,
+                            letterboxing.projection_size.height,
+                        );
+                        let mut custom_size = state.level.size.is_some();
+                        ui.label("Size:");
+                        ui.vertical(|ui| {
+                            ui.checkbox(&mut custom_size, "Custom");
+                            let mut size = state.level.bounds(screen);
+                            if custom_size {
+                                vec2_input_ui(ui, &mut size);
+                                if ui
+                                    .button("Fit to Objects")
+                                    .on_hover_text("The smallest size around every object")
+                                    .clicked()
+                                {
+                                    // The walled area is centered on the origin.
+                                    let bounding_box = state.level.bounding_box();
+                                    size = 2.0 * bounding_box.min.abs().max(bounding_box.max.abs());
+                                }
+                            }
+                            state.level.size = custom_size.then_some(size.max(Vec2::ONE));
+                        });
+                        ui.end_row();
+
+                        ui.label("Camera:");
+                        ui.checkbox(&mut state.level.camera_follow, "Follow player");
+                        ui.end_row();
 
-                        ui.label("Author:");
-                        ui.add(
-                            egui::TextEdit::singleline(author)
-                                .hint_text("None")
-                                .desired_width(150.0),
-                        );
+                        let mut has_time_limit = state.level.time_limit.is_some();
+                        ui.label("Time Limit:");
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut has_time_limit, "");
+                            let mut secs = state.level.time_limit.map_or(30.0, |t| t.as_secs_f32());
+                            if has_time_limit {
+                                ui.add(
+                                    egui::DragValue::new(&mut secs)
+                                        .range(1.0..=600.0)
+                                        .suffix(" s"),
+                                );
+                            }
+                            state.level.time_limit =
+                                has_time_limit.then(|| Duration::from_secs_f32(secs));
+                        });
+                        ui.end_row();
 
-                        state.level.author = (!author.is_empty()).then_some(author.clone());
+                        ui.label("Objective:");
+                        objective_input_ui(ui, &mut state.level.objective);
+                        ui.end_row();
+
+                        ui.label("Wall Bounciness:");
+                        ui.add(egui::Slider::new(
+                            &mut state.level.wall_restitution,
+                            0.0..=1.5,
+                        ));
                     });
 
+                if name_taken {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        "A custom level with this name already exists.",
+                    );
+                }
+
                 ui.separator();
 
                 ui.horizontal(|ui| {
                     ui.selectable_value(&mut state.mode, EditorMode::Place, "Place");
                     ui.selectable_value(&mut state.mode, EditorMode::Select, "Select");
+                    ui.selectable_value(
+                        &mut state.mode,
+                        EditorMode::TestFromCursor,
+                        "Test from cursor",
+                    );
+                });
+
+                egui::CollapsingHeader::new("Shortcuts")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        for (key, action) in [
+                            (PLACE_PARTICLE_KEY, "Place particles"),
+                            (PLACE_OBSTACLE_KEY, "Place obstacles"),
+                            (TOGGLE_MODE_KEY, "Toggle place and select"),
+                        ] {
+                            ui.label(format!("{}: {action}", key_name(key)));
+                        }
+                        ui.label("Ctrl + Z: Undo");
+                        ui.label("Ctrl + Shift + Z: Redo");
+                        ui.label("Ctrl + D: Duplicate");
+                        ui.label("Shift + click: Add to or remove from the selection");
+                        ui.label("Click again: Select the object below");
+                        ui.label("Middle mouse drag: Pan the view");
+                        ui.label("Scroll: Zoom the view");
+                        ui.label("Arrow keys: Move the selection, further with Shift");
+                    });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut state.alignment_snapping, "Snap to objects");
+                    ui.add_enabled(
+                        state.alignment_snapping,
+                        egui::DragValue::new(&mut state.alignment_tolerance)
+                            .range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut state.snap_enabled, "Snap to grid");
+                    ui.add_enabled(
+                        state.snap_enabled,
+                        egui::DragValue::new(&mut state.grid_size)
+                            .range(0.0..=f32::MAX)
+                            .speed(0.1),
+                    );
                 });
 
                 ui.separator();
 
                 match state.mode {
                     EditorMode::Place => {
-                        state.selected = None;
+                        state.deselect();
 
                         ui.horizontal(|ui| {
                             ui.selectable_value(
@@ -567,7 +2004,34 @@ fn editor_ui(
                         });
                     }
                     EditorMode::Select => {
-                        if let Some(selected) = state.selected {
+                        if 1 < state.selection.len() {
+                            ui.label(format!("{} objects selected", state.selection.len()));
+                            if ui.button("Delete Selected").clicked() {
+                                state.delete_selection();
+                            }
+                        } else if let Some(selected) = state.selected {
+                            if selected != PreviewIndex::Player {
+                                // These change the index of the selected object.
+                                let mut reindexed = false;
+                                ui.horizontal(|ui| {
+                                    if ui.button("Duplicate").clicked() {
+                                        state.duplicate_selected();
+                                        reindexed = true;
+                                    }
+                                    if ui.button("Bring to Front").clicked() {
+                                        state.reorder_selected(true);
+                                        reindexed = true;
+                                    }
+                                    if ui.button("Send to Back").clicked() {
+                                        state.reorder_selected(false);
+                                        reindexed = true;
+                                    }
+                                });
+                                if reindexed {
+                                    return;
+                                }
+                            }
+
                             match selected {
                                 PreviewIndex::Player => {
                                     egui::Grid::new("player_grid")
@@ -575,40 +2039,69 @@ fn editor_ui(
                                         .spacing([10.0, 8.0])
                                         .show(ui, |ui| {
                                             ui.label("Position:");
-                                            vec2_input_ui(ui, &mut state.level.player_spawn);
+                                            let grid_size = state.snap_grid();
+                                            position_input_ui(
+                                                ui,
+                                                &mut state.level.player_spawn,
+                                                grid_size,
+                                            );
                                             ui.end_row();
                                         });
                                 }
                                 PreviewIndex::Particle(index) => {
+                                    let grid_size = state.snap_grid();
                                     let particle_data =
                                         state.level.particles.get_mut(index).unwrap();
 
-                                    ui.label("Position:");
-                                    vec2_input_ui(ui, &mut particle_data.spawn_position);
+                                    ui.horizontal(|ui| {
+                                        ui.label("Label:");
+                                        label_input_ui(ui, &mut particle_data.label);
+                                    });
 
-                                    if particle_ui(ui, true, index, &mut particle_data.particle)
-                                        .is_some()
+                                    ui.label("Position:");
+                                    position_input_ui(
+                                        ui,
+                                        &mut particle_data.spawn_position,
+                                        grid_size,
+                                    );
+
+                                    if particle_ui(
+                                        ui,
+                                        true,
+                                        index,
+                                        &[],
+                                        &mut particle_data.particle,
+                                        &mut state.velocity_target,
+                                    )
+                                    .is_some()
                                     {
+                                        state.record_history();
                                         state.level.particles.remove(index);
-                                        state.selected = None;
+                                        state.deselect();
                                     }
                                 }
                                 PreviewIndex::Obstacle(index) => {
                                     if ui.button("Delete").clicked() {
+                                        state.record_history();
                                         state.level.obstacles.remove(index);
-                                        state.selected = None;
+                                        state.deselect();
                                         return;
                                     }
 
+                                    let grid_size = state.snap_grid();
                                     let obstacle = state.level.obstacles.get_mut(index).unwrap();
 
                                     egui::Grid::new("obstacle_grid")
                                         .num_columns(2)
                                         .spacing([10.0, 8.0])
                                         .show(ui, |ui| {
+                                            ui.label("Label:");
+                                            label_input_ui(ui, &mut obstacle.label);
+                                            ui.end_row();
+
                                             ui.label("Position:");
                                             let mut position = obstacle.transform.translation.xy();
-                                            vec2_input_ui(ui, &mut position);
+                                            position_input_ui(ui, &mut position, grid_size);
                                             obstacle.transform.translation = position.extend(0.0);
                                             ui.end_row();
 
@@ -634,16 +2127,47 @@ fn editor_ui(
                                                 Color::srgb_u8(color[0], color[1], color[2]);
                                             ui.end_row();
 
-                                            ui.label("Width:");
-                                            ui.add(egui::DragValue::new(&mut obstacle.width));
+                                            ui.label("Shape:");
+                                            obstacle_shape_input_ui(ui, obstacle);
                                             ui.end_row();
 
-                                            ui.label("Height:");
-                                            ui.add(egui::DragValue::new(&mut obstacle.height));
-                                            ui.end_row();
+                                            match &mut obstacle.shape {
+                                                ObstacleShape::Rectangle => {
+                                                    ui.label("Width:");
+                                                    ui.add(egui::DragValue::new(
+                                                        &mut obstacle.width,
+                                                    ));
+                                                    ui.end_row();
+
+                                                    ui.label("Height:");
+                                                    ui.add(egui::DragValue::new(
+                                                        &mut obstacle.height,
+                                                    ));
+                                                    ui.end_row();
+                                                }
+                                                ObstacleShape::Circle { radius } => {
+                                                    ui.label("Radius:");
+                                                    ui.add(
+                                                        egui::DragValue::new(radius)
+                                                            .range(1.0..=f32::MAX),
+                                                    );
+                                                    ui.end_row();
+                                                }
+                                            }
 
                                             ui.checkbox(&mut obstacle.is_killer, "Is Killer");
                                             ui.end_row();
+
+                                            let mut is_trigger = obstacle.trigger.is_some();
+                                            ui.checkbox(&mut is_trigger, "Is Trigger");
+                                            if is_trigger != obstacle.trigger.is_some() {
+                                                obstacle.trigger =
+                                                    is_trigger.then(TriggerData::default);
+                                            }
+                                            if let Some(trigger) = &mut obstacle.trigger {
+                                                ui.text_edit_singleline(&mut trigger.name);
+                                            }
+                                            ui.end_row();
                                         });
                                 }
                             }
@@ -651,22 +2175,60 @@ fn editor_ui(
                             ui.label("Nothing selected");
                         }
                     }
+                    EditorMode::TestFromCursor => {
+                        state.deselect();
+                        ui.label("Click where the player should start.");
+                    }
                 }
             });
         });
 }
 
+/// Lists the warnings of the last "Check level", each with a button selecting the offending object.
+fn warnings_ui(ui: &mut Ui, state: &mut EditorState) {
+    let Some(warnings) = &state.warnings else {
+        return;
+    };
+
+    if warnings.is_empty() {
+        ui.label("No problems found.");
+        return;
+    }
+
+    let mut to_select = None;
+    for warning in warnings {
+        ui.horizontal(|ui| {
+            ui.colored_label(egui::Color32::YELLOW, warning.to_string());
+            if ui.button("Select").clicked() {
+                // Select the later object, that is usually the accidental copy.
+                to_select = Some(match *warning {
+                    LevelWarning::PlayerSpawnOutOfBounds => PreviewIndex::Player,
+                    LevelWarning::DuplicateObstacles(_, index) => PreviewIndex::Obstacle(index),
+                    LevelWarning::DuplicateParticles(_, index) => PreviewIndex::Particle(index),
+                });
+            }
+        });
+    }
+
+    // The level might have changed since it was checked.
+    if let Some(to_select) = to_select.filter(|&index| state.object_exists(index)) {
+        state.mode = EditorMode::Select;
+        state.select(to_select);
+    }
+}
+
 fn refresh_level_preview(mut commands: Commands) {
     commands.trigger(SpawnLevelPreview);
 }
 
-fn mouse_world_position(
+pub(super) fn mouse_world_position(
     window_query: &Query<&Window>,
     camera_query: &Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
     letterboxing: &Letterboxing,
 ) -> Option<Vec2> {
-    let window = window_query.single().unwrap();
-    let (camera, camera_transform) = camera_query.single().unwrap();
+    // The gameplay camera may not exist yet while the editor is being set up.
+    let window = window_query.single().ok()?;
+    let (camera, camera_transform) = camera_query.single().ok()?;
 
     let window_size = Size::new(window.width(), window.height());
     let actual_size = letterbox(window_size, letterboxing.aspect_ratio);
@@ -697,6 +2259,73 @@ fn mouse_world_position(
         .map(|p| p.xy())
 }
 
+fn editor_shortcuts(
+    mut contexts: EguiContexts,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    // Typing into a text field shouldn't switch modes.
+    if contexts.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+
+    if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        if keyboard_input.just_pressed(KeyCode::KeyD) {
+            editor_state.duplicate_selected();
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyZ) {
+            if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+                editor_state.redo();
+            } else {
+                editor_state.undo();
+            }
+        }
+        return;
+    }
+
+    for &key in keyboard_input.get_just_pressed() {
+        editor_state.apply_shortcut(key);
+    }
+}
+
+/// How far the arrow keys move the selected objects, a grid cell instead if snapping is on.
+const NUDGE_STEP: f32 = 1.0;
+/// Holding shift moves them this many steps at once.
+const LARGE_NUDGE_STEPS: f32 = 10.0;
+
+fn nudge_selected_objects(
+    mut contexts: EguiContexts,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    if editor_state.mode != EditorMode::Select || contexts.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+
+    let direction: Vec2 = [
+        (KeyCode::ArrowLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, Vec2::X),
+        (KeyCode::ArrowDown, Vec2::NEG_Y),
+        (KeyCode::ArrowUp, Vec2::Y),
+    ]
+    .into_iter()
+    .filter(|&(key, _)| keyboard_input.just_pressed(key))
+    .map(|(_, direction)| direction)
+    .sum();
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let mut step = editor_state
+        .snap_grid()
+        .filter(|&grid_size| 0.0 < grid_size)
+        .unwrap_or(NUDGE_STEP);
+    if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        step *= LARGE_NUDGE_STEPS;
+    }
+    editor_state.nudge_selection(step * direction);
+}
+
 fn object_placement(
     mut editor_state: ResMut<EditorState>,
     mut contexts: EguiContexts,
@@ -719,10 +2348,15 @@ fn object_placement(
         return;
     }
 
-    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+    let Some(mut position) = mouse_world_position(&window_query, &camera_query, &letterboxing)
+    else {
         return;
     };
+    if let Some(grid_size) = editor_state.snap_grid() {
+        position = snap_to_grid(position, grid_size);
+    }
 
+    editor_state.record_history();
     match editor_state.placement {
         Object::Particle => {
             editor_state
@@ -739,15 +2373,218 @@ fn object_placement(
     }
 }
 
+/// How far the velocity handle is from the particle per unit of initial velocity.
+const VELOCITY_HANDLE_SCALE: f32 = 0.5;
+const VELOCITY_HANDLE_RADIUS: f32 = 8.0;
+/// The fastest initial velocity the handle sets, the bundled levels stay below 1000.
+const MAX_HANDLE_VELOCITY: f32 = 1500.0;
+
+/// The initial velocity of a particle at `origin` whose velocity handle is at `handle`.
+fn handle_velocity(origin: Vec2, handle: Vec2) -> Vec2 {
+    ((handle - origin) / VELOCITY_HANDLE_SCALE).clamp_length_max(MAX_HANDLE_VELOCITY)
+}
+
+/// Drags the velocity handle of the selected (sub)particle, setting its initial velocity.
+fn drag_velocity_handle(
+    mut editor_state: ResMut<EditorState>,
+    mut contexts: EguiContexts,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        editor_state.dragging_velocity = false;
+        return;
+    }
+
+    let Some((origin, handle)) = editor_state.velocity_handle() else {
+        editor_state.dragging_velocity = false;
+        return;
+    };
+
+    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+        return;
+    };
+
+    if !editor_state.dragging_velocity {
+        if !mouse_buttons.just_pressed(MouseButton::Left)
+            || contexts.ctx_mut().is_pointer_over_area()
+            || handle.distance(position) > VELOCITY_HANDLE_RADIUS
+        {
+            return;
+        }
+        editor_state.drag_moved = false;
+        editor_state.dragging_velocity = true;
+    }
+
+    editor_state.drag_velocity_to(handle_velocity(origin, position));
+}
+
+/// Drags the selected object, aligning it with the other objects if snapping is enabled.
+fn drag_selected_object(
+    mut editor_state: ResMut<EditorState>,
+    mut contexts: EguiContexts,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+    player_config: Res<PlayerConfig>,
+) {
+    let selected = editor_state
+        .selected
+        .filter(|_| editor_state.mode == EditorMode::Select);
+    let (Some(selected), true) = (selected, mouse_buttons.pressed(MouseButton::Left)) else {
+        if !mouse_buttons.pressed(MouseButton::Left) {
+            editor_state.release_click(player_config.radius);
+        }
+        editor_state.object_drag = None;
+        editor_state.alignment_guides = (None, None);
+        return;
+    };
+
+    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+        return;
+    };
+    let Some(object_position) = editor_state.object_position(selected) else {
+        return;
+    };
+
+    let grab_offset = match editor_state.object_drag {
+        Some(grab_offset) => grab_offset,
+        None => {
+            if !mouse_buttons.just_pressed(MouseButton::Left)
+                || editor_state.dragging_velocity
+                || contexts.ctx_mut().is_pointer_over_area()
+                || !editor_state.object_contains(selected, position, player_config.radius)
+            {
+                return;
+            }
+            let grab_offset = object_position - position;
+            editor_state.drag_moved = false;
+            editor_state.object_drag = Some(grab_offset);
+            grab_offset
+        }
+    };
+
+    let mut target = position + grab_offset;
+    editor_state.alignment_guides = (None, None);
+
+    if editor_state.alignment_snapping {
+        let (dragged, others): (Vec<_>, Vec<_>) = editor_state
+            .alignment_keys(player_config.radius)
+            .into_iter()
+            .partition(|(index, _)| *index == selected);
+        let Some((_, mut dragged)) = dragged.into_iter().next() else {
+            return;
+        };
+
+        // The keys are where the object would be without snapping.
+        let shift = target - object_position;
+        dragged.xs.iter_mut().for_each(|x| *x += shift.x);
+        dragged.ys.iter_mut().for_each(|y| *y += shift.y);
+
+        let others: Vec<_> = others.into_iter().map(|(_, keys)| keys).collect();
+        let alignment = align(&dragged, &others, editor_state.alignment_tolerance);
+        target += alignment.offset;
+        editor_state.alignment_guides = (alignment.guide_x, alignment.guide_y);
+    }
+
+    editor_state.drag_object_to(selected, target);
+}
+
+const ALIGNMENT_GUIDE_COLOR: Color = Color::srgb(0.2, 0.8, 1.0);
+
+fn draw_alignment_guides(
+    editor_state: Res<EditorState>,
+    letterboxing: Res<Letterboxing>,
+    mut gizmos: Gizmos,
+) {
+    let half_size = vec2(
+        letterboxing.projection_size.width,
+        letterboxing.projection_size.height,
+    ) / 2.0;
+
+    let (guide_x, guide_y) = editor_state.alignment_guides;
+    if let Some(x) = guide_x {
+        gizmos.line_2d(
+            vec2(x, -half_size.y),
+            vec2(x, half_size.y),
+            ALIGNMENT_GUIDE_COLOR,
+        );
+    }
+    if let Some(y) = guide_y {
+        gizmos.line_2d(
+            vec2(-half_size.x, y),
+            vec2(half_size.x, y),
+            ALIGNMENT_GUIDE_COLOR,
+        );
+    }
+}
+
+const SELECTION_MARKER_SIZE: f32 = 12.0;
+
+/// Marks each object of a selection of several objects.
+fn draw_selection(editor_state: Res<EditorState>, mut gizmos: Gizmos) {
+    if editor_state.selection.len() < 2 {
+        return;
+    }
+
+    for &index in &editor_state.selection {
+        let Some(position) = editor_state.object_position(index) else {
+            continue;
+        };
+        gizmos.cross_2d(
+            Isometry2d::from_translation(position),
+            SELECTION_MARKER_SIZE,
+            Color::WHITE,
+        );
+    }
+}
+
+fn draw_velocity_handle(editor_state: Res<EditorState>, mut gizmos: Gizmos) {
+    let Some((origin, handle)) = editor_state.velocity_handle() else {
+        return;
+    };
+
+    gizmos.arrow_2d(origin, handle, Color::WHITE);
+    gizmos.circle_2d(handle, VELOCITY_HANDLE_RADIUS, Color::WHITE);
+}
+
+/// Plays the level with the player spawned at the clicked position.
+fn test_from_cursor(
+    mut editor_state: ResMut<EditorState>,
+    mut contexts: EguiContexts,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+    mut events: EventWriter<EditorEvent>,
+) {
+    if editor_state.mode != EditorMode::TestFromCursor
+        || !mouse_buttons.just_pressed(MouseButton::Left)
+        || contexts.ctx_mut().is_pointer_over_area()
+    {
+        return;
+    }
+
+    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+        return;
+    };
+
+    editor_state.test_spawn = Some(position);
+    events.write(EditorEvent::Play);
+}
+
 #[derive(Component)]
 pub struct EditorPointer;
 
-fn spawn_editor_pointer(mut commands: Commands) {
-    commands.spawn((
+fn editor_pointer() -> impl Bundle {
+    (
         StateScoped(Screen::Editor),
         EditorPointer,
         PointerId::Custom(Uuid::new_v4()),
-    ));
+    )
 }
 
 pub fn editor_pointer_picking(
@@ -760,8 +2597,11 @@ pub fn editor_pointer_picking(
     mut cursor_last: Local<Vec2>,
     mut pointer_events: EventWriter<PointerInput>,
 ) {
-    let pointer_id = pointer_query.single().unwrap();
-    let handle = &gameplay_render_target.single().unwrap().0;
+    let (Ok(pointer_id), Ok(GameplayRenderTarget(handle))) =
+        (pointer_query.single(), gameplay_render_target.single())
+    else {
+        return;
+    };
 
     for window_event in window_events.read() {
         match window_event {
@@ -771,7 +2611,9 @@ pub fn editor_pointer_picking(
                     continue;
                 }
 
-                let window = window_query.single().unwrap();
+                let Ok(window) = window_query.single() else {
+                    continue;
+                };
                 let window_size = Size::new(window.width(), window.height());
                 let actual_size = letterbox(window_size, letterboxing.aspect_ratio);
 
@@ -852,7 +2694,446 @@ fn select(
     trigger: Trigger<Pointer<Pressed>>,
     mut editor_state: ResMut<EditorState>,
     preview_index_query: Query<&PreviewIndex>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_config: Res<PlayerConfig>,
 ) {
+    // The middle mouse button pans the camera.
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+
+    // Grabbing the velocity handle shouldn't select what is below it.
+    let on_velocity_handle = editor_state
+        .velocity_handle()
+        .zip(trigger.hit.position)
+        .is_some_and(|((_, handle), hit)| handle.distance(hit.xy()) <= VELOCITY_HANDLE_RADIUS);
+    if on_velocity_handle {
+        return;
+    }
+
     let preview_index = preview_index_query.get(trigger.target).unwrap();
-    editor_state.selected = Some(*preview_index);
+    if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        editor_state.toggle_selection(*preview_index);
+    } else if let Some(hit) = trigger.hit.position {
+        editor_state.press_select(*preview_index, hit.xy(), player_config.radius);
+    } else {
+        editor_state.select(*preview_index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, state::app::StatesPlugin};
+
+    use super::*;
+
+    #[test]
+    fn deleting_a_selection_removes_every_selected_object() {
+        let mut state = EditorState::default();
+        for x in 0..3 {
+            let position = vec2(x as f32, 0.0);
+            state
+                .level
+                .particles
+                .push(ParticleData::default_at(position));
+            state
+                .level
+                .obstacles
+                .push(ObstacleData::default_at(position));
+        }
+
+        state.select(PreviewIndex::Particle(0));
+        state.toggle_selection(PreviewIndex::Particle(2));
+        state.toggle_selection(PreviewIndex::Obstacle(1));
+        state.toggle_selection(PreviewIndex::Player);
+        state.toggle_selection(PreviewIndex::Player);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(1)));
+
+        state.delete_selection();
+        assert_eq!(state.level.particles.len(), 1);
+        assert_eq!(state.level.particles[0].spawn_position, vec2(1.0, 0.0));
+        let obstacles: Vec<_> = state
+            .level
+            .obstacles
+            .iter()
+            .map(|obstacle| obstacle.transform.translation.x)
+            .collect();
+        assert_eq!(obstacles, [0.0, 2.0]);
+        assert!(state.selected.is_none() && state.selection.is_empty());
+    }
+
+    #[test]
+    fn nudging_respects_the_grid() {
+        let mut state = EditorState::default();
+        state
+            .level
+            .particles
+            .push(ParticleData::default_at(vec2(3.0, 0.0)));
+        state.select(PreviewIndex::Particle(0));
+
+        state.nudge_selection(vec2(1.0, 0.0));
+        assert_eq!(state.level.particles[0].spawn_position, vec2(4.0, 0.0));
+
+        state.snap_enabled = true;
+        state.nudge_selection(vec2(0.0, state.grid_size));
+        assert_eq!(state.level.particles[0].spawn_position, vec2(0.0, 10.0));
+    }
+
+    #[test]
+    fn duplicates_are_offset_and_selected() {
+        let mut state = EditorState::default();
+        let particle_data = ParticleData::new(
+            Vec2::ZERO,
+            Particle {
+                subparticles: vec![Particle::default()],
+                ..default()
+            },
+        );
+        state.level.particles.push(particle_data);
+
+        state.select(PreviewIndex::Player);
+        state.duplicate_selected();
+        assert_eq!(state.level.particles.len(), 1);
+
+        state.select(PreviewIndex::Particle(0));
+        state.duplicate_selected();
+        let copy = &state.level.particles[1];
+        assert_eq!(copy.spawn_position, DUPLICATE_OFFSET);
+        assert_eq!(copy.particle.subparticles.len(), 1);
+        assert!(state.selected == Some(PreviewIndex::Particle(1)));
+    }
+
+    #[test]
+    fn clicking_the_same_spot_cycles_through_overlapping_objects() {
+        let mut state = EditorState::default();
+        state.level.player_spawn = vec2(500.0, 0.0);
+        state
+            .level
+            .obstacles
+            .push(ObstacleData::default_at(Vec2::ZERO));
+        state
+            .level
+            .obstacles
+            .push(ObstacleData::default_at(Vec2::ZERO));
+        state
+            .level
+            .particles
+            .push(ParticleData::default_at(Vec2::ZERO));
+
+        let mut clicks = Vec::new();
+        for _ in 0..4 {
+            state.click_select(PreviewIndex::Particle(0), Vec2::ONE, 20.0);
+            clicks.extend(state.selected);
+        }
+        assert!(
+            clicks
+                == [
+                    PreviewIndex::Particle(0),
+                    PreviewIndex::Obstacle(1),
+                    PreviewIndex::Obstacle(0),
+                    PreviewIndex::Particle(0),
+                ]
+        );
+
+        // Clicking elsewhere selects what was picked.
+        state.click_select(PreviewIndex::Obstacle(0), vec2(20.0, 20.0), 20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(0)));
+    }
+
+    #[test]
+    fn pressing_the_selected_object_drags_it_instead_of_cycling() {
+        let mut state = EditorState {
+            mode: EditorMode::Select,
+            ..default()
+        };
+        state.level.player_spawn = vec2(500.0, 0.0);
+        for _ in 0..2 {
+            state
+                .level
+                .obstacles
+                .push(ObstacleData::default_at(Vec2::ZERO));
+        }
+
+        state.press_select(PreviewIndex::Obstacle(1), Vec2::ONE, 20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(1)));
+        state.release_click(20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(1)));
+
+        // Clicking again cycles to the object below, only once the click is released.
+        state.press_select(PreviewIndex::Obstacle(1), Vec2::ONE, 20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(1)));
+        state.release_click(20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(0)));
+
+        // Pressing it again to drag it keeps it selected.
+        state.press_select(PreviewIndex::Obstacle(1), Vec2::ONE, 20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(0)));
+        state.drag_object_to(PreviewIndex::Obstacle(0), vec2(40.0, 0.0));
+        state.release_click(20.0);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(0)));
+        assert_eq!(state.level.obstacles[0].transform.translation.x, 40.0);
+    }
+
+    #[test]
+    fn clicking_without_moving_keeps_the_history() {
+        let mut state = EditorState::default();
+        state
+            .level
+            .obstacles
+            .push(ObstacleData::default_at(Vec2::ZERO));
+        state.select(PreviewIndex::Obstacle(0));
+        state.nudge_selection(vec2(10.0, 0.0));
+        state.nudge_selection(vec2(10.0, 0.0));
+        state.undo();
+
+        // A click starts a drag that doesn't move the object.
+        state.drag_moved = false;
+        state.drag_object_to(PreviewIndex::Obstacle(0), vec2(10.0, 0.0));
+
+        state.redo();
+        assert_eq!(state.level.obstacles[0].transform.translation.x, 20.0);
+        state.undo();
+        state.undo();
+        assert_eq!(state.level.obstacles[0].transform.translation.x, 0.0);
+        assert!(!state.history.undo(&mut state.level));
+
+        // Moving it is a single step, however many frames the drag takes.
+        state.drag_moved = false;
+        state.drag_object_to(PreviewIndex::Obstacle(0), vec2(5.0, 0.0));
+        state.drag_object_to(PreviewIndex::Obstacle(0), vec2(15.0, 0.0));
+        state.undo();
+        assert_eq!(state.level.obstacles[0].transform.translation.x, 0.0);
+        assert!(!state.history.undo(&mut state.level));
+    }
+
+    #[test]
+    fn reordering_moves_the_selected_object() {
+        let mut state = EditorState::default();
+        for x in [0.0, 100.0, 200.0] {
+            state
+                .level
+                .obstacles
+                .push(ObstacleData::default_at(vec2(x, 0.0)));
+        }
+
+        state.select(PreviewIndex::Obstacle(0));
+        state.reorder_selected(true);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(2)));
+        assert_eq!(state.level.obstacles[2].transform.translation.x, 0.0);
+
+        state.reorder_selected(false);
+        assert!(state.selected == Some(PreviewIndex::Obstacle(0)));
+        assert_eq!(state.level.obstacles[0].transform.translation.x, 0.0);
+        assert_eq!(state.level.obstacles[1].transform.translation.x, 100.0);
+    }
+
+    #[test]
+    fn handle_velocities_are_clamped() {
+        let origin = vec2(100.0, 0.0);
+        assert_eq!(
+            handle_velocity(origin, origin + vec2(0.0, 50.0)),
+            vec2(0.0, 50.0 / VELOCITY_HANDLE_SCALE)
+        );
+        assert_eq!(
+            handle_velocity(origin, origin + vec2(-10_000.0, 0.0)),
+            vec2(-MAX_HANDLE_VELOCITY, 0.0)
+        );
+    }
+
+    #[test]
+    fn positions_snap_to_the_nearest_grid_point() {
+        assert_eq!(snap_to_grid(vec2(14.0, -16.0), 10.0), vec2(10.0, -20.0));
+        assert_eq!(snap_to_grid(vec2(14.0, -16.0), 0.0), vec2(14.0, -16.0));
+    }
+
+    #[test]
+    fn loading_invalid_ron_keeps_the_level() {
+        let mut state = EditorState::default();
+        state.level.name = String::from("Kept");
+
+        load_ron(&mut state, "(name: ");
+        assert_eq!(state.level.name, "Kept");
+        assert!(state.load_error.is_some());
+
+        let ron = ron::ser::to_string(&LevelData::example()).unwrap();
+        load_ron(&mut state, &ron);
+        assert_eq!(state.level.name, LevelData::example().name);
+        assert!(state.load_error.is_none());
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn saved_levels_are_named_after_the_level() {
+        assert_eq!(
+            level_file_name(" My Level 2 ").as_deref(),
+            Some("my_level_2")
+        );
+        assert_eq!(level_file_name("../up").as_deref(), Some("up"));
+        assert_eq!(level_file_name("   "), None);
+        assert_eq!(level_file_name("./"), None);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn saving_never_replaces_another_level() {
+        let dir = std::env::temp_dir().join(format!("antim4tter-save-{}", std::process::id()));
+        let named = |name: &str| LevelData {
+            name: String::from(name),
+            ..default()
+        };
+
+        let SaveResult::Saved(path) = save_level(&dir, &named("My Level"), None) else {
+            panic!("the level wasn't saved");
+        };
+        assert!(matches!(
+            save_level(&dir, &named("my_level"), Some(&path)),
+            SaveResult::Failed(_)
+        ));
+        assert!(matches!(
+            save_level(&dir, &named("My Level"), None),
+            SaveResult::Exists(_)
+        ));
+        assert!(matches!(
+            save_level(&dir, &named("My Level"), Some(&path)),
+            SaveResult::Saved(_)
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn spawn_editor_spawns_the_preview_and_the_pointer() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Mesh>();
+        app.init_asset::<ColorMaterial>();
+        app.init_resource::<EditorState>();
+        app.init_resource::<PlayerConfig>();
+        app.add_observer(spawn_editor);
+        app.add_observer(spawn_level_preview);
+
+        app.world_mut().trigger(SpawnEditor);
+        app.world_mut().flush();
+
+        let world = app.world_mut();
+        assert!(world.resource::<EditorState>().editing);
+        assert_eq!(
+            world
+                .query_filtered::<(), With<LevelPreview>>()
+                .iter(world)
+                .count(),
+            1
+        );
+        assert_eq!(
+            world
+                .query_filtered::<(), With<EditorPointer>>()
+                .iter(world)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn the_view_survives_playing_the_level() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin));
+        app.init_asset::<Mesh>();
+        app.init_asset::<ColorMaterial>();
+        app.init_state::<Screen>();
+        app.init_resource::<EditorState>();
+        app.init_resource::<PlayerConfig>();
+        app.init_resource::<SelectedLevel>();
+        app.add_event::<EditorEvent>();
+        app.add_observer(spawn_editor);
+        app.add_observer(spawn_level_preview);
+        app.add_observer(|_: Trigger<SpawnRawLevel>| {});
+        let mut projection = Projection::Orthographic(OrthographicProjection::default_2d());
+        set_camera_scale(&mut projection, 2.0);
+        let camera = app
+            .world_mut()
+            .spawn((
+                GameplayCamera,
+                Transform::from_xyz(30.0, 40.0, 0.0),
+                projection,
+            ))
+            .id();
+
+        let mut editor_state = app.world_mut().resource_mut::<EditorState>();
+        editor_state
+            .level
+            .obstacles
+            .push(ObstacleData::default_at(Vec2::ZERO));
+        editor_state.mode = EditorMode::Select;
+        editor_state.select(PreviewIndex::Player);
+        editor_state.toggle_selection(PreviewIndex::Obstacle(0));
+
+        app.world_mut().send_event(EditorEvent::Play);
+        app.world_mut()
+            .run_system_once(handle_editor_event_play)
+            .unwrap();
+
+        // Ending the level resets the camera, the view is restored regardless of what changed.
+        app.world_mut()
+            .get_mut::<Transform>(camera)
+            .unwrap()
+            .translation = Vec3::ZERO;
+        set_camera_scale(
+            &mut app.world_mut().get_mut::<Projection>(camera).unwrap(),
+            1.0,
+        );
+        app.world_mut().resource_mut::<EditorState>().deselect();
+        app.world_mut().trigger(SpawnEditor);
+        app.world_mut().flush();
+
+        let editor_state = app.world().resource::<EditorState>();
+        assert!(editor_state.mode == EditorMode::Select);
+        assert!(editor_state.selected == Some(PreviewIndex::Obstacle(0)));
+        assert!(editor_state.selection == [PreviewIndex::Player, PreviewIndex::Obstacle(0)]);
+        assert_eq!(
+            app.world().get::<Transform>(camera).unwrap().translation,
+            vec3(30.0, 40.0, 0.0)
+        );
+        assert_eq!(
+            camera_scale(app.world().get::<Projection>(camera).unwrap()),
+            2.0
+        );
+    }
+
+    #[test]
+    fn shortcuts_switch_the_placement_and_the_mode() {
+        let mut editor_state = EditorState {
+            mode: EditorMode::Select,
+            ..default()
+        };
+
+        editor_state.apply_shortcut(PLACE_OBSTACLE_KEY);
+        assert!(editor_state.mode == EditorMode::Place);
+        assert!(editor_state.placement == Object::Obstacle);
+
+        editor_state.apply_shortcut(TOGGLE_MODE_KEY);
+        assert!(editor_state.mode == EditorMode::Select);
+        assert!(editor_state.placement == Object::Obstacle);
+
+        editor_state.apply_shortcut(PLACE_PARTICLE_KEY);
+        assert!(editor_state.mode == EditorMode::Place);
+        assert!(editor_state.placement == Object::Particle);
+    }
+
+    #[test]
+    fn no_mouse_position_without_a_gameplay_camera() {
+        let mut world = World::new();
+        world.init_resource::<Letterboxing>();
+        world.spawn(Window::default());
+
+        let position = world
+            .run_system_once(
+                |window_query: Query<&Window>,
+                 camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+                 letterboxing: Res<Letterboxing>| {
+                    mouse_world_position(&window_query, &camera_query, &letterboxing)
+                },
+            )
+            .unwrap();
+        assert_eq!(position, None);
+    }
 }
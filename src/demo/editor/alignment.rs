@@ -0,0 +1,95 @@
+//! Aligning a dragged object with the other objects of the level, like design tools do.
+
+use bevy::prelude::*;
+
+/// The coordinates other objects align with, the center and the edges of an object.
+#[derive(Default)]
+pub struct AlignmentKeys {
+    pub xs: Vec<f32>,
+    pub ys: Vec<f32>,
+}
+
+impl AlignmentKeys {
+    pub fn circle(center: Vec2, radius: f32) -> Self {
+        Self {
+            xs: vec![center.x - radius, center.x, center.x + radius],
+            ys: vec![center.y - radius, center.y, center.y + radius],
+        }
+    }
+
+    /// Uses the bounding box of the corners, so rotated obstacles align by their extents.
+    pub fn polygon(center: Vec2, corners: &[Vec2]) -> Self {
+        let min = corners.iter().copied().fold(Vec2::MAX, Vec2::min);
+        let max = corners.iter().copied().fold(Vec2::MIN, Vec2::max);
+
+        Self {
+            xs: vec![min.x, center.x, max.x],
+            ys: vec![min.y, center.y, max.y],
+        }
+    }
+}
+
+/// How to move a dragged object to align it, and the coordinates of the guides to show.
+#[derive(Default, Debug, PartialEq)]
+pub struct Alignment {
+    pub offset: Vec2,
+    pub guide_x: Option<f32>,
+    pub guide_y: Option<f32>,
+}
+
+/// Aligns the closest keys of `dragged` with keys of `others` on each axis, if they are within
+/// `tolerance`.
+pub fn align(dragged: &AlignmentKeys, others: &[AlignmentKeys], tolerance: f32) -> Alignment {
+    let snap_x = snap_axis(
+        &dragged.xs,
+        others.iter().flat_map(|keys| &keys.xs),
+        tolerance,
+    );
+    let snap_y = snap_axis(
+        &dragged.ys,
+        others.iter().flat_map(|keys| &keys.ys),
+        tolerance,
+    );
+
+    Alignment {
+        offset: vec2(
+            snap_x.map_or(0.0, |(shift, _)| shift),
+            snap_y.map_or(0.0, |(shift, _)| shift),
+        ),
+        guide_x: snap_x.map(|(_, coordinate)| coordinate),
+        guide_y: snap_y.map(|(_, coordinate)| coordinate),
+    }
+}
+
+/// The smallest shift aligning one of `keys` with one of `anchors`, with the aligned coordinate.
+fn snap_axis<'a>(
+    keys: &[f32],
+    anchors: impl Iterator<Item = &'a f32>,
+    tolerance: f32,
+) -> Option<(f32, f32)> {
+    anchors
+        .flat_map(|&anchor| keys.iter().map(move |&key| (anchor - key, anchor)))
+        .filter(|(shift, _)| shift.abs() <= tolerance)
+        .min_by(|(a, _), (b, _)| a.abs().total_cmp(&b.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_the_closest_edge_within_tolerance() {
+        let dragged = AlignmentKeys::circle(vec2(3.0, 100.0), 10.0);
+        let others = [AlignmentKeys::polygon(
+            Vec2::ZERO,
+            &[vec2(-20.0, -20.0), vec2(20.0, 20.0)],
+        )];
+
+        let alignment = align(&dragged, &others, 5.0);
+
+        // The centers are 3 apart, the edges of the circle are further from any key.
+        assert_eq!(alignment.offset, vec2(-3.0, 0.0));
+        assert_eq!(alignment.guide_x, Some(0.0));
+        assert_eq!(alignment.guide_y, None);
+    }
+}
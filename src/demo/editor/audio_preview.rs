@@ -0,0 +1,64 @@
+//! An editor window listing the loaded sounds and music, to listen to them while picking.
+
+use bevy::prelude::*;
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContextPass, EguiContexts},
+    egui,
+};
+
+use crate::{audio::sound_effect, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        EguiContextPass,
+        audio_preview_ui.run_if(in_state(Screen::Editor)),
+    );
+}
+
+/// Marker for audio played from the preview window, so it can be stopped.
+#[derive(Component)]
+struct AudioPreview;
+
+/// Lists every loaded [`AudioSource`], which covers the handles held by all the asset resources.
+fn audio_preview_ui(
+    mut contexts: EguiContexts,
+    audio_sources: Res<Assets<AudioSource>>,
+    asset_server: Res<AssetServer>,
+    preview_query: Query<Entity, With<AudioPreview>>,
+    mut commands: Commands,
+) {
+    let mut sources: Vec<_> = audio_sources
+        .ids()
+        .filter_map(|id| Some((asset_server.get_path(id)?.to_string(), id)))
+        .collect();
+    sources.sort();
+
+    egui::Window::new("Audio")
+        .default_pos([10.0, 600.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if ui.button("Stop All").clicked() {
+                for entity in preview_query.iter() {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (path, id) in sources {
+                    ui.horizontal(|ui| {
+                        // Played like any sound effect, so the volume settings apply.
+                        let handle = ui
+                            .button("Play")
+                            .clicked()
+                            .then(|| asset_server.get_id_handle(id))
+                            .flatten();
+                        if let Some(handle) = handle {
+                            commands.spawn((sound_effect(handle), AudioPreview));
+                        }
+                        ui.label(path);
+                    });
+                }
+            });
+        });
+}
@@ -0,0 +1,118 @@
+//! Panning and zooming the gameplay camera in the editor, to work on levels larger than the screen.
+//!
+//! The cursor is converted with [`mouse_world_position`], which goes through the camera, so placing
+//! and dragging objects keeps working wherever the camera is.
+
+use bevy::{
+    input::mouse::{AccumulatedMouseScroll, MouseScrollUnit},
+    prelude::*,
+};
+use bevy_inspector_egui::bevy_egui::EguiContexts;
+
+use super::mouse_world_position;
+use crate::{
+    camera::{GameplayCamera, Letterboxing},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (pan_camera, zoom_camera).run_if(in_state(Screen::Editor)),
+    );
+    // The rest of the game is played from the origin, at the normal zoom.
+    app.add_systems(OnExit(Screen::Editor), reset_view);
+}
+
+const MIN_SCALE: f32 = 0.25;
+const MAX_SCALE: f32 = 4.0;
+/// How much one notch of the scroll wheel zooms.
+const ZOOM_STEP: f32 = 1.1;
+/// Pixel scrolling, e.g. from touchpads, per notch of a scroll wheel.
+const PIXELS_PER_LINE: f32 = 100.0;
+
+/// The scale of the camera projection, larger shows more of the level.
+pub(super) fn camera_scale(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Orthographic(orthographic) => orthographic.scale,
+        _ => 1.0,
+    }
+}
+
+pub(super) fn set_camera_scale(projection: &mut Projection, scale: f32) {
+    if let Projection::Orthographic(orthographic) = projection {
+        orthographic.scale = scale;
+    }
+}
+
+/// The scale after scrolling by `lines`, scrolling up zooms in.
+fn zoomed(scale: f32, lines: f32) -> f32 {
+    (scale * ZOOM_STEP.powf(-lines)).clamp(MIN_SCALE, MAX_SCALE)
+}
+
+/// Drags the level with the middle mouse button, keeping the grabbed point under the cursor.
+fn pan_camera(
+    mut grabbed: Local<Option<Vec2>>,
+    mut contexts: EguiContexts,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+    mut camera_transform: Single<&mut Transform, With<GameplayCamera>>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Middle) {
+        *grabbed = None;
+        return;
+    }
+
+    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+        return;
+    };
+
+    if mouse_buttons.just_pressed(MouseButton::Middle) && !contexts.ctx_mut().is_pointer_over_area()
+    {
+        *grabbed = Some(position);
+    }
+
+    if let Some(grabbed) = *grabbed {
+        camera_transform.translation += (grabbed - position).extend(0.0);
+    }
+}
+
+fn zoom_camera(
+    scroll: Res<AccumulatedMouseScroll>,
+    mut contexts: EguiContexts,
+    mut projection: Single<&mut Projection, With<GameplayCamera>>,
+) {
+    // Scrolling over the panels scrolls them instead.
+    if scroll.delta.y == 0.0 || contexts.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+
+    let lines = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / PIXELS_PER_LINE,
+    };
+    let scale = zoomed(camera_scale(&projection), lines);
+    set_camera_scale(&mut projection, scale);
+}
+
+/// Coming back from playing the level restores the view, see `EditorView`.
+fn reset_view(camera: Single<(&mut Transform, &mut Projection), With<GameplayCamera>>) {
+    let (mut transform, mut projection) = camera.into_inner();
+    transform.translation = Vec3::ZERO.with_z(transform.translation.z);
+    set_camera_scale(&mut projection, 1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_is_clamped() {
+        assert!(zoomed(1.0, 1.0) < 1.0);
+        assert!(zoomed(1.0, -1.0) > 1.0);
+        assert_eq!(zoomed(1.0, 100.0), MIN_SCALE);
+        assert_eq!(zoomed(1.0, -100.0), MAX_SCALE);
+    }
+}
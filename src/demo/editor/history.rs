@@ -0,0 +1,102 @@
+//! Undo and redo of changes to the edited level.
+
+use std::collections::VecDeque;
+
+use crate::demo::level::level_data::LevelData;
+
+/// The most changes that can be undone, older ones are forgotten.
+const MAX_HISTORY: usize = 50;
+
+/// Snapshots of the level from before each change, and of the undone changes.
+#[derive(Default)]
+pub struct History {
+    undo: VecDeque<LevelData>,
+    redo: Vec<LevelData>,
+}
+
+impl History {
+    /// Keeps `level` to return to, call before changing it.
+    ///
+    /// A new change can't be redone after, so the redo stack is cleared.
+    pub fn record(&mut self, level: &LevelData) {
+        if self.undo.len() == MAX_HISTORY {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(level.clone());
+        self.redo.clear();
+    }
+
+    /// Returns `level` to its state before the last change, `false` if there was none.
+    pub fn undo(&mut self, level: &mut LevelData) -> bool {
+        let Some(previous) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(std::mem::replace(level, previous));
+        true
+    }
+
+    /// Applies the last undone change to `level` again, `false` if there was none.
+    pub fn redo(&mut self, level: &mut LevelData) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(std::mem::replace(level, next));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> LevelData {
+        LevelData {
+            name: String::from(name),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut history = History::default();
+        let mut level = named("a");
+
+        history.record(&level);
+        level = named("b");
+        history.record(&level);
+        level = named("c");
+
+        assert!(history.undo(&mut level));
+        assert_eq!(level.name, "b");
+        assert!(history.undo(&mut level));
+        assert_eq!(level.name, "a");
+        assert!(!history.undo(&mut level));
+
+        assert!(history.redo(&mut level));
+        assert_eq!(level.name, "b");
+
+        // A new change drops what was undone.
+        history.record(&level);
+        level = named("d");
+        assert!(!history.redo(&mut level));
+        assert!(history.undo(&mut level));
+        assert_eq!(level.name, "b");
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = History::default();
+        let mut level = named("0");
+        for i in 1..=MAX_HISTORY + 10 {
+            history.record(&level);
+            level = named(&i.to_string());
+        }
+
+        let mut undone = 0;
+        while history.undo(&mut level) {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_HISTORY);
+        assert_eq!(level.name, "10");
+    }
+}
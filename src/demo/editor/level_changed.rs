@@ -0,0 +1,99 @@
+//! Lets external tools follow the level being edited, e.g. to mirror it in a live preview.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use super::EditorState;
+use crate::{AppSystems, demo::level::level_data::LevelData, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<EditorLevelChanged>();
+    app.init_resource::<LevelChangeDebounce>();
+
+    app.add_systems(
+        Update,
+        send_level_changed
+            .run_if(in_state(Screen::Editor))
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// Sent with the edited level after it changed, at most once per [`MIN_INTERVAL`].
+#[derive(Event)]
+pub struct EditorLevelChanged {
+    pub level: LevelData,
+}
+
+/// Keeps continuous edits like dragging from flooding listeners.
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Changes are detected by hashing the serialized level, as the editor mutates it in many places.
+#[derive(Resource, Default)]
+struct LevelChangeDebounce {
+    last_hash: Option<u64>,
+    since_check: Duration,
+}
+
+impl LevelChangeDebounce {
+    /// Whether `level` changed since the last time this returned `true`, checked only once
+    /// [`MIN_INTERVAL`] passed.
+    fn changed(&mut self, level: &LevelData, delta: Duration) -> bool {
+        self.since_check += delta;
+        if self.since_check < MIN_INTERVAL {
+            return false;
+        }
+        self.since_check = Duration::ZERO;
+
+        let Ok(serialized) = ron::to_string(level) else {
+            return false;
+        };
+        let mut hasher = DefaultHasher::new();
+        serialized.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if self.last_hash == Some(hash) {
+            return false;
+        }
+        self.last_hash = Some(hash);
+        true
+    }
+}
+
+fn send_level_changed(
+    editor_state: Res<EditorState>,
+    mut debounce: ResMut<LevelChangeDebounce>,
+    time: Res<Time>,
+    mut events: EventWriter<EditorLevelChanged>,
+) {
+    if debounce.changed(&editor_state.level, time.delta()) {
+        events.write(EditorLevelChanged {
+            level: editor_state.level.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_sent_at_most_once_per_interval() {
+        let mut debounce = LevelChangeDebounce::default();
+        let mut level = LevelData::default();
+        let frame = Duration::from_millis(100);
+
+        assert!(!debounce.changed(&level, frame));
+        assert!(!debounce.changed(&level, frame));
+        assert!(debounce.changed(&level, frame));
+
+        level.camera_follow = !level.camera_follow;
+        assert!(!debounce.changed(&level, frame));
+        assert!(!debounce.changed(&level, frame));
+        assert!(debounce.changed(&level, frame));
+        assert!(!debounce.changed(&level, MIN_INTERVAL));
+    }
+}
@@ -0,0 +1,134 @@
+//! Particles as shown in the editor, with their arrows but without physics.
+
+use bevy::{
+    ecs::{relationship::RelatedSpawner, spawn::SpawnWith},
+    prelude::*,
+};
+
+use super::{PreviewIndex, select};
+use crate::{
+    demo::{
+        killer::KillerLook,
+        particle::{
+            Particle, ParticleConfig, ParticleKind,
+            arrows::{Arrows, ArrowsAssets, ArrowsConfig, ArrowsOf, arrows},
+        },
+    },
+    external::maybe::Maybe,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_particle_preview);
+}
+
+#[derive(Event)]
+pub struct SpawnParticlePreview {
+    pub index: usize,
+    pub translation: Vec2,
+    pub particle: Particle,
+    pub parent: Option<Entity>,
+}
+
+fn spawn_particle_preview(
+    mut trigger: Trigger<SpawnParticlePreview>,
+    particle_config: Res<ParticleConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    arrows_config: Res<ArrowsConfig>,
+    arrows_assets: Res<ArrowsAssets>,
+    mut commands: Commands,
+) {
+    commands
+        .spawn((
+            PreviewIndex::Particle(trigger.index),
+            particle_preview_bundle(
+                trigger.translation,
+                std::mem::take(&mut trigger.particle),
+                &particle_config,
+                meshes.as_mut(),
+                materials.as_mut(),
+                &arrows_config,
+                &arrows_assets,
+            ),
+            // The subparticle will have the same parent as the particle if it has a parent.
+            Maybe(trigger.parent.map(ChildOf)),
+        ))
+        .observe(select);
+}
+
+pub fn particle_preview_bundle(
+    translation: Vec2,
+    particle: Particle,
+    particle_config: &ParticleConfig,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    arrows_config: &ArrowsConfig,
+    arrows_assets: &ArrowsAssets,
+) -> impl Bundle {
+    let spawn_list = {
+        let arrows_config = *arrows_config;
+        let arrows_assets = arrows_assets.clone();
+        let particle = particle.clone();
+
+        move |spawner: &mut RelatedSpawner<ArrowsOf>| {
+            spawner.spawn(arrows(
+                translation,
+                &particle,
+                &arrows_config,
+                &arrows_assets,
+            ));
+        }
+    };
+
+    (
+        Arrows::spawn(SpawnWith(spawn_list)),
+        particle_preview(translation, particle, particle_config, meshes, materials),
+    )
+}
+
+/// Uses the same [`ParticleConfig::local_z`] as gameplay particles, so previews overlap other
+/// objects the same way.
+pub fn particle_preview(
+    translation: Vec2,
+    particle: Particle,
+    particle_config: &ParticleConfig,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) -> impl Bundle {
+    let mesh = meshes.add(particle.shape.mesh(particle.radius));
+    let material = materials.add(particle.color);
+
+    (
+        Name::new("Particle"),
+        Transform::from_translation(translation.extend(particle_config.local_z)),
+        Mesh2d(mesh),
+        MeshMaterial2d(material),
+        Maybe((particle.kind == ParticleKind::Killer).then_some(KillerLook)),
+        particle,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_use_the_gameplay_depth() {
+        let mut world = World::new();
+        let config = ParticleConfig::default();
+        let mut meshes = Assets::<Mesh>::default();
+        let mut materials = Assets::<ColorMaterial>::default();
+        let preview = world
+            .spawn(particle_preview(
+                vec2(10.0, 20.0),
+                Particle::default(),
+                &config,
+                &mut meshes,
+                &mut materials,
+            ))
+            .id();
+
+        let transform = world.get::<Transform>(preview).unwrap();
+        assert_eq!(transform.translation, vec3(10.0, 20.0, config.local_z));
+    }
+}
@@ -0,0 +1,130 @@
+//! Faint lines showing the path of the player in the previous attempts at the level.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        level::{Level, SpawnRawLevel},
+        player::Player,
+    },
+    screens::Screen,
+    settings::Settings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GhostTrails>();
+
+    app.add_observer(rotate_ghost_trails);
+    app.add_systems(
+        Update,
+        (
+            record_ghost_trail
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+            draw_ghost_trails,
+        )
+            .run_if(in_state(Screen::Gameplay).and(ghost_trails_enabled)),
+    );
+}
+
+/// How many previous attempts are shown.
+const MAX_GHOSTS: usize = 3;
+/// The most positions kept per attempt, the oldest are dropped first.
+const MAX_TRAIL_POINTS: usize = 2048;
+/// How far the player has to move before another position is recorded.
+const MIN_POINT_SPACING: f32 = 4.0;
+const GHOST_ALPHA: f32 = 0.3;
+
+/// The path of the current attempt and of the previous attempts at the same level.
+#[derive(Resource, Default)]
+struct GhostTrails {
+    level: Option<Level>,
+    current: VecDeque<Vec2>,
+    /// The most recent attempt first.
+    previous: VecDeque<Vec<Vec2>>,
+}
+
+impl GhostTrails {
+    fn record(&mut self, position: Vec2) {
+        if self
+            .current
+            .back()
+            .is_some_and(|last| last.distance(position) < MIN_POINT_SPACING)
+        {
+            return;
+        }
+
+        if self.current.len() == MAX_TRAIL_POINTS {
+            self.current.pop_front();
+        }
+        self.current.push_back(position);
+    }
+
+    /// Keeps the current attempt as a ghost when `level` is restarted, forgets all ghosts otherwise.
+    fn start_attempt(&mut self, level: Option<Level>) {
+        let current = std::mem::take(&mut self.current);
+
+        // Levels played from the editor may have changed in between.
+        if level.is_none() || level != self.level {
+            self.previous.clear();
+            self.level = level;
+            return;
+        }
+
+        if current.len() > 1 {
+            self.previous.push_front(current.into());
+            self.previous.truncate(MAX_GHOSTS);
+        }
+    }
+}
+
+fn ghost_trails_enabled(settings: Res<Settings>) -> bool {
+    settings.ghost_trails
+}
+
+fn rotate_ghost_trails(trigger: Trigger<SpawnRawLevel>, mut trails: ResMut<GhostTrails>) {
+    trails.start_attempt(trigger.level.clone());
+}
+
+fn record_ghost_trail(
+    player_query: Query<&GlobalTransform, With<Player>>,
+    mut trails: ResMut<GhostTrails>,
+) {
+    if let Ok(transform) = player_query.single() {
+        trails.record(transform.translation().xy());
+    }
+}
+
+fn draw_ghost_trails(trails: Res<GhostTrails>, mut gizmos: Gizmos) {
+    for (i, trail) in trails.previous.iter().enumerate() {
+        // Older attempts fade out.
+        let alpha = GHOST_ALPHA * (1.0 - i as f32 / MAX_GHOSTS as f32);
+        gizmos.linestrip_2d(trail.iter().copied(), Color::WHITE.with_alpha(alpha));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_restarts_keep_ghosts() {
+        let mut trails = GhostTrails::default();
+        let level = Some(Level::Default(0));
+
+        for attempt in 0..5 {
+            trails.start_attempt(level.clone());
+            trails.record(Vec2::ZERO);
+            trails.record(vec2(10.0, attempt as f32));
+        }
+        trails.start_attempt(level.clone());
+        assert_eq!(trails.previous.len(), MAX_GHOSTS);
+        assert_eq!(trails.previous[0][1], vec2(10.0, 4.0));
+
+        trails.start_attempt(Some(Level::Default(1)));
+        assert!(trails.previous.is_empty());
+    }
+}
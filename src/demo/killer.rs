@@ -1,4 +1,4 @@
-use bevy::{ecs::system::QueryLens, prelude::*};
+use bevy::{prelude::*, render::mesh::MeshAabb};
 use bevy_rapier2d::prelude::*;
 
 use crate::{
@@ -6,13 +6,26 @@ use crate::{
     demo::player::Player,
     physics::{CollisionHandlerSystems, find_rigidbody_ancestor},
     screens::Screen,
+    settings::Settings,
+    theme::palette::Theme,
 };
 
-use super::time_scale::{SetTimeScale, SetTimeScaleOverride, TimeScaleKind};
+use super::time_scale::{SetTimeScale, SetTimeScaleOverride, reset_time_scale};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<KillEvent>();
 
+    app.init_resource::<KillerAssets>();
+    app.add_observer(add_killer_outline);
+    app.add_observer(remove_killer_outline);
+    app.add_systems(
+        Update,
+        (
+            refresh_killer_outlines.run_if(resource_changed::<Settings>),
+            update_outline_material.run_if(resource_changed::<Theme>),
+        ),
+    );
+
     app.add_systems(
         PostUpdate,
         (
@@ -26,49 +39,183 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
+#[require(KillerLook)]
 pub struct Killer;
 
+/// Anything that should look like a killer, including editor previews.
+///
+/// In colorblind mode these get an outline, so they can be told apart without relying on color.
+#[derive(Component, Default)]
+pub struct KillerLook;
+
+/// Width of the outline drawn around killers in colorblind mode.
+const OUTLINE_WIDTH: f32 = 4.0;
+
+#[derive(Component)]
+struct KillerOutline;
+
+#[derive(Resource)]
+struct KillerAssets {
+    /// Colored from the current [`Theme`].
+    outline_material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for KillerAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+
+        Self {
+            outline_material: materials.add(ColorMaterial::default()),
+        }
+    }
+}
+
+/// The same mesh as the killer stretched behind it, so it shows as an outline of constant width.
+fn killer_outline(
+    killer: Entity,
+    mesh: Handle<Mesh>,
+    meshes: &Assets<Mesh>,
+    killer_assets: &KillerAssets,
+) -> Option<impl Bundle> {
+    let half_size = meshes.get(&mesh)?.compute_aabb()?.half_extents;
+    if half_size.x <= 0.0 || half_size.y <= 0.0 {
+        return None;
+    }
+
+    Some((
+        Name::new("Killer Outline"),
+        KillerOutline,
+        Transform::from_xyz(0.0, 0.0, -0.1).with_scale(Vec3::new(
+            (half_size.x + OUTLINE_WIDTH) / half_size.x,
+            (half_size.y + OUTLINE_WIDTH) / half_size.y,
+            1.0,
+        )),
+        Mesh2d(mesh),
+        MeshMaterial2d(killer_assets.outline_material.clone()),
+        ChildOf(killer),
+    ))
+}
+
+fn add_killer_outline(
+    trigger: Trigger<OnAdd, KillerLook>,
+    mesh_query: Query<&Mesh2d>,
+    settings: Res<Settings>,
+    meshes: Res<Assets<Mesh>>,
+    killer_assets: Res<KillerAssets>,
+    mut commands: Commands,
+) {
+    if !settings.colorblind_mode {
+        return;
+    }
+
+    let Ok(mesh) = mesh_query.get(trigger.target()) else {
+        return;
+    };
+
+    if let Some(outline) = killer_outline(trigger.target(), mesh.0.clone(), &meshes, &killer_assets)
+    {
+        commands.spawn(outline);
+    }
+}
+
+fn remove_killer_outline(
+    trigger: Trigger<OnRemove, KillerLook>,
+    children_query: Query<&Children>,
+    outline_query: Query<(), With<KillerOutline>>,
+    mut commands: Commands,
+) {
+    let Ok(children) = children_query.get(trigger.target()) else {
+        return;
+    };
+
+    for &child in children {
+        if outline_query.contains(child) {
+            // The killer itself might be getting despawned together with its children.
+            commands.entity(child).try_despawn();
+        }
+    }
+}
+
+fn update_outline_material(
+    killer_assets: Res<KillerAssets>,
+    theme: Res<Theme>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if let Some(material) = materials.get_mut(&killer_assets.outline_material) {
+        material.color = theme.hazard.killer_outline;
+    }
+}
+
+/// Rebuilds the outlines when colorblind mode is toggled, new killers are outlined when added.
+fn refresh_killer_outlines(
+    outline_query: Query<Entity, With<KillerOutline>>,
+    killer_query: Query<(Entity, &Mesh2d), With<KillerLook>>,
+    settings: Res<Settings>,
+    meshes: Res<Assets<Mesh>>,
+    killer_assets: Res<KillerAssets>,
+    mut colorblind_mode: Local<bool>,
+    mut commands: Commands,
+) {
+    // Any other setting changing leaves the outlines as they are.
+    if *colorblind_mode == settings.colorblind_mode {
+        return;
+    }
+    *colorblind_mode = settings.colorblind_mode;
+
+    for outline in outline_query.iter() {
+        commands.entity(outline).despawn();
+    }
+
+    if !settings.colorblind_mode {
+        return;
+    }
+
+    for (killer, mesh) in killer_query.iter() {
+        if let Some(outline) = killer_outline(killer, mesh.0.clone(), &meshes, &killer_assets) {
+            commands.spawn(outline);
+        }
+    }
+}
+
 #[derive(Event)]
 pub struct KillEvent {
     pub player: Entity,
 }
 
-fn killer_collision_handler(
+pub(super) fn killer_collision_handler(
     mut collision_events: EventReader<CollisionEvent>,
-    mut query: Query<(
-        Option<&Killer>,
-        Option<&Player>,
-        Option<&RigidBody>,
-        &ChildOf,
-    )>,
+    ancestor_query: Query<(Option<&RigidBody>, Option<&ChildOf>)>,
+    query: Query<(Has<Killer>, Has<Player>), Or<(With<Killer>, With<Player>)>>,
     mut events: EventWriter<KillEvent>,
 ) {
+    let _span = debug_span!("killer_collision_handler").entered();
     for event in collision_events.read() {
         let CollisionEvent::Started(e1, e2, _) = *event else {
-            return;
+            continue;
         };
 
-        let mut helper_lens: QueryLens<(Option<&RigidBody>, &ChildOf)> = query.transmute_lens();
-        let helper_query = helper_lens.query();
-        let Some(e1) = find_rigidbody_ancestor(e1, &helper_query) else {
-            return;
+        let Some(e1) = find_rigidbody_ancestor(e1, &ancestor_query) else {
+            continue;
         };
-        let Some(e2) = find_rigidbody_ancestor(e2, &helper_query) else {
-            return;
+        let Some(e2) = find_rigidbody_ancestor(e2, &ancestor_query) else {
+            continue;
         };
 
-        let (e1_killer, e1_player, _, _) = query.get(e1).unwrap();
-        let (e2_killer, e2_player, _, _) = query.get(e2).unwrap();
+        // Skip pairs that don't involve both a killer and the player.
+        let (Ok((e1_killer, e1_player)), Ok((e2_killer, e2_player))) =
+            (query.get(e1), query.get(e2))
+        else {
+            continue;
+        };
 
-        if e1_killer.is_some() && e2_player.is_some() {
+        if e1_killer && e2_player {
             events.write(KillEvent { player: e2 });
-            return;
+            continue;
         }
 
-        if e2_killer.is_some() && e1_player.is_some() {
+        if e2_killer && e1_player {
             events.write(KillEvent { player: e1 });
-            return;
         }
     }
 }
@@ -80,9 +227,9 @@ fn kill(
     mut commands: Commands,
 ) {
     for event in events.read() {
+        debug!(player = ?event.player, "Player died");
         commands.entity(event.player).despawn();
 
-        time_override_events.write(SetTimeScaleOverride(None));
-        time_events.write(SetTimeScale(TimeScaleKind::Normal));
+        reset_time_scale(&mut time_events, &mut time_override_events);
     }
 }
@@ -5,11 +5,20 @@ use std::time::Duration;
 use bevy::audio::Volume;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use camera_follow::CameraFollow;
 use level_data::LevelData;
 use level_loading::LevelAssets;
+use objective::ObjectiveProgress;
+use time_limit::TimeLimit;
 
+pub mod camera_follow;
 pub mod level_data;
 pub mod level_loading;
+pub mod objective;
+pub mod run;
+pub mod stats;
+pub mod time_limit;
+pub mod trigger;
 
 use crate::asset_tracking::LoadResource;
 use crate::audio::{SoundEffect, sound_effect};
@@ -20,46 +29,98 @@ use crate::{
     AppSystems, PausableSystems,
     audio::music::{GameplayMusic, MusicAssets, gameplay_music},
     camera::Letterboxing,
-    demo::particle::{ParticleDespawned, ParticleSpawned},
+    demo::particle::{
+        Particle, ParticleDespawned, ParticleKind, ParticleSpawned, PopSounds,
+        invincible::Invincible,
+    },
     demo::player::player,
     external::maybe::Maybe,
+    key_bindings::KeyBindings,
     screens::Screen,
 };
 
-use super::editor::EditorState;
-use super::player::Player;
-use super::time_scale::{SetTimeScale, SetTimeScaleOverride, TimeScaleKind};
+use super::player::{Player, PlayerState};
+use super::time_scale::{Game, SetTimeScale, SetTimeScaleOverride, reset_time_scale};
 
-pub(super) fn plugin(app: &mut App) {
-    app.register_type::<LevelAudioAssets>();
-    app.register_type::<ParticleCount>();
+/// Level loading, spawning, restarting and completion.
+///
+/// Can be added by other crates with a custom [`LevelConfig`].
+#[derive(Default)]
+pub struct LevelPlugin {
+    pub config: LevelConfig,
+}
 
-    app.load_resource::<LevelAudioAssets>();
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LevelConfig>();
+        app.register_type::<LevelAudioAssets>();
+        app.register_type::<ParticleCount>();
 
-    app.add_plugins((level_data::plugin, level_loading::plugin));
+        app.insert_resource(self.config.clone());
+        app.load_resource::<LevelAudioAssets>();
 
-    app.add_observer(spawn_level);
-    app.add_observer(spawn_raw_level);
+        app.add_plugins((
+            camera_follow::plugin,
+            level_data::plugin,
+            level_loading::plugin,
+            objective::plugin,
+            run::plugin,
+            stats::plugin,
+            time_limit::plugin,
+            trigger::plugin,
+        ));
 
-    app.add_systems(
-        Update,
-        restart_level
-            .run_if(in_state(Screen::Gameplay))
-            .in_set(AppSystems::Update)
-            .in_set(PausableSystems),
-    );
+        app.add_observer(spawn_level);
+        app.add_observer(spawn_raw_level);
 
-    app.add_event::<EndLevel>();
-    app.add_event::<EndGame>();
-    app.add_systems(
-        Update,
-        (
-            (increase_particle_count, decrease_particle_count).chain(),
-            (tick_end_level_timer, end_level, end_game).chain(),
-        )
-            .run_if(in_state(Screen::Gameplay))
-            .in_set(AppSystems::Update),
-    );
+        app.add_systems(
+            Update,
+            restart_level
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        );
+
+        app.add_event::<EndLevel>();
+        app.add_event::<EndGame>();
+        app.add_systems(
+            Update,
+            (
+                (
+                    increase_particle_count,
+                    decrease_particle_count,
+                    complete_objective,
+                )
+                    .chain(),
+                (tick_end_level_timer, end_level, end_game).chain(),
+            )
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(AppSystems::Update),
+        );
+
+        #[cfg(feature = "dev")]
+        app.add_systems(
+            Update,
+            (skip_level, copy_level_snapshot)
+                .run_if(in_state(Screen::Gameplay))
+                .in_set(AppSystems::Update),
+        );
+    }
+}
+
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct LevelConfig {
+    /// How long to wait after the last particle is gone before ending the level.
+    pub end_level_delay: Duration,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            end_level_delay: Duration::from_secs_f32(2.0),
+        }
+    }
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -95,12 +156,22 @@ pub enum LevelState {
 
 // TODO Add custom levels to level selection menu.
 #[allow(dead_code)]
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
 pub enum Level {
     Default(usize),
     Custom(String),
 }
 
+impl Level {
+    /// How the level is called when its data isn't at hand, e.g. `Level 1`.
+    pub fn fallback_name(&self) -> String {
+        match self {
+            Self::Default(id) => format!("Level {}", id + 1),
+            Self::Custom(name) => name.clone(),
+        }
+    }
+}
+
 #[derive(Event)]
 pub struct SpawnLevel(pub Level);
 
@@ -117,9 +188,14 @@ pub fn spawn_level(
 
     let level_data = levels.get(level_handle).unwrap();
 
+    let source = match &trigger.0 {
+        Level::Default(_) => PlaySource::Campaign,
+        Level::Custom(_) => PlaySource::Custom,
+    };
     commands.trigger(SpawnRawLevel {
         data: level_data.clone(),
         level: Some(trigger.0.clone()),
+        source,
     });
 }
 
@@ -127,12 +203,52 @@ pub fn spawn_level(
 pub struct SpawnRawLevel {
     pub data: LevelData,
     pub level: Option<Level>,
+    pub source: PlaySource,
+}
+
+/// Where the level being played was started from, kept on the level across restarts.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaySource {
+    Campaign,
+    Custom,
+    /// Played from the editor, returning to it once over.
+    EditorTest,
+    Sandbox,
+    /// The demo run of the attract mode, returning to the title screen once over.
+    Demo,
+}
+
+impl PlaySource {
+    /// Whether playing counts towards the player's progress, like scores and best times.
+    ///
+    /// Test plays from the editor, the sandbox and the demo run don't.
+    pub fn records_progress(&self) -> bool {
+        matches!(self, Self::Campaign | Self::Custom)
+    }
+
+    /// The screen to return to once the level is over without a next level.
+    pub fn return_screen(&self) -> Screen {
+        match self {
+            Self::EditorTest => Screen::Editor,
+            Self::Demo => Screen::Title,
+            Self::Campaign | Self::Custom | Self::Sandbox => Screen::Levels,
+        }
+    }
 }
 
+/// The working copy of the level data the level was spawned from.
 #[derive(Component)]
-#[require(ParticleCount, LevelState)]
+#[require(ParticleCount, LevelState, stats::LevelStats)]
 pub struct RawLevel(pub LevelData);
 
+/// An untouched copy of the level data the level was spawned from.
+///
+/// Restarts are sourced from this, so changes made to the level while playing
+/// can never leak into the next attempt.
+#[derive(Component)]
+#[component(immutable)]
+pub struct PristineLevel(pub LevelData);
+
 /// A system that spawns the main level.
 pub fn spawn_raw_level(
     mut trigger: Trigger<SpawnRawLevel>,
@@ -142,21 +258,66 @@ pub fn spawn_raw_level(
     music_query: Query<Entity, With<GameplayMusic>>,
     music_assets: Res<MusicAssets>,
     letterboxing: Res<Letterboxing>,
+    previous_level_query: Query<Entity, Or<(With<RawLevel>, With<LevelBounds>)>>,
+    mut pop_sounds: ResMut<PopSounds>,
+    mut next_screen: ResMut<NextState<Screen>>,
     mut commands: Commands,
 ) {
-    let level_data = std::mem::take(&mut trigger.data);
+    let _span = debug_span!("spawn_raw_level", level = ?trigger.level).entered();
+    // Only one level is played at a time, the previous one goes along with its walls.
+    for previous_level in previous_level_query.iter() {
+        commands.entity(previous_level).despawn();
+    }
 
-    // Spawn screen bounds first
-    commands.spawn(screen_bounds(&letterboxing));
+    let level_data = std::mem::take(&mut trigger.data);
+    let screen = vec2(
+        letterboxing.projection_size.width,
+        letterboxing.projection_size.height,
+    );
+    if !level_data.player_spawn_in_bounds(screen) {
+        warn!(
+            "The player of level {} spawns outside of the screen at {}.",
+            level_data.name, level_data.player_spawn
+        );
+    }
+    debug!(
+        obstacles = level_data.obstacles.len(),
+        particles = level_data.particles.len(),
+        "Level started"
+    );
+    // The level is still spawned, so gameplay can rely on there being one until the screen changes.
+    if level_data.particles.is_empty() && level_data.objective.needs_particles() {
+        error!(
+            "Level {} has no particles and nothing to do, leaving it.",
+            level_data.name
+        );
+        next_screen.set(trigger.source.return_screen());
+    }
 
     if music_query.is_empty() {
         commands.spawn((gameplay_music(&music_assets), StateScoped(Screen::Gameplay)));
     }
+    pop_sounds.muted = !level_data.pop_sounds;
+
+    commands.spawn((
+        level_bounds(level_data.bounds(screen), level_data.wall_restitution),
+        LevelBounds,
+        StateScoped(Screen::Gameplay),
+    ));
 
     let level = commands
         .spawn((
             Name::new("Level"),
             Maybe(trigger.level.clone()),
+            trigger.source,
+            Maybe(
+                level_data
+                    .time_limit
+                    .map(|remaining| TimeLimit { remaining }),
+            ),
+            Maybe(level_data.camera_follow.then(|| CameraFollow {
+                size: level_data.bounds(screen),
+            })),
             Transform::default(),
             Visibility::default(),
             StateScoped(Screen::Gameplay),
@@ -180,16 +341,19 @@ pub fn spawn_raw_level(
         .id();
 
     for obstacle_data in level_data.obstacles.iter() {
-        let material = materials.add(obstacle_data.color);
-        let mesh = meshes.add(Rectangle::new(obstacle_data.width, obstacle_data.height));
+        let material = materials.add(obstacle_data.display_color());
+        let mesh = meshes.add(obstacle_data.mesh());
 
         let obstacle = commands
-            .spawn(obstacle(
-                obstacle_data.transform,
-                material,
-                mesh,
-                Collider::cuboid(obstacle_data.width / 2.0, obstacle_data.height / 2.0),
-                obstacle_data.is_killer,
+            .spawn((
+                obstacle(
+                    obstacle_data.transform,
+                    material,
+                    mesh,
+                    obstacle_data.collider(),
+                    obstacle_data.is_killer,
+                ),
+                Maybe(obstacle_data.trigger.as_ref().map(trigger::trigger_zone)),
             ))
             .id();
 
@@ -201,13 +365,19 @@ pub fn spawn_raw_level(
             translation: particle_data.spawn_position,
             particle: particle_data.particle.clone(),
             spawn_with_invincible: false,
+            show_arrows: level_data.show_arrows,
             parent: Some(level),
         });
     }
 
-    commands.entity(level).insert(RawLevel(level_data));
+    commands
+        .entity(level)
+        .insert((PristineLevel(level_data.clone()), RawLevel(level_data)));
 }
 
+/// How much particles and the player bounce off obstacles.
+const OBSTACLE_RESTITUTION: f32 = 0.25;
+
 pub fn obstacle(
     transform: Transform,
     material: Handle<ColorMaterial>,
@@ -222,6 +392,10 @@ pub fn obstacle(
         MeshMaterial2d(material),
         RigidBody::Fixed,
         collider,
+        Restitution {
+            coefficient: OBSTACLE_RESTITUTION,
+            combine_rule: CoefficientCombineRule::Max,
+        },
         {
             if !is_killer {
                 CollisionGroups::new(Group::GROUP_1, Group::all())
@@ -233,18 +407,22 @@ pub fn obstacle(
     )
 }
 
-fn screen_bounds(letterboxing: &Letterboxing) -> impl Bundle {
-    let width = letterboxing.projection_size.width;
-    let height = letterboxing.projection_size.height;
+/// Marker for the walls of the level being played, see [`spawn_raw_level`].
+#[derive(Component)]
+pub struct LevelBounds;
 
-    let halfwidth = width / 2.0;
-    let halfheight = height / 2.0;
+/// Walls around an area of `size`, centered on the origin.
+///
+/// The wall `restitution` takes precedence over the restitution of whatever hits the walls, which
+/// is 0 for particles and the player, so walls can both absorb and amplify bounces.
+pub fn level_bounds(size: Vec2, restitution: f32) -> impl Bundle {
+    let halfwidth = size.x / 2.0;
+    let halfheight = size.y / 2.0;
 
     let thickness = 1.0;
-    let restitution = 0.5;
 
     (
-        Name::new("Screen Bounds"),
+        Name::new("Level Bounds"),
         Transform::default(),
         children![
             (
@@ -253,7 +431,10 @@ fn screen_bounds(letterboxing: &Letterboxing) -> impl Bundle {
                 RigidBody::Fixed,
                 Collider::cuboid(thickness, halfheight),
                 CollisionGroups::new(Group::GROUP_1, Group::all()),
-                Restitution::coefficient(restitution),
+                Restitution {
+                    coefficient: restitution,
+                    combine_rule: CoefficientCombineRule::Max,
+                },
             ),
             (
                 Name::new("Right Wall"),
@@ -261,7 +442,10 @@ fn screen_bounds(letterboxing: &Letterboxing) -> impl Bundle {
                 RigidBody::Fixed,
                 Collider::cuboid(thickness, halfheight),
                 CollisionGroups::new(Group::GROUP_1, Group::all()),
-                Restitution::coefficient(restitution),
+                Restitution {
+                    coefficient: restitution,
+                    combine_rule: CoefficientCombineRule::Max,
+                },
             ),
             (
                 Name::new("Top Wall"),
@@ -269,7 +453,10 @@ fn screen_bounds(letterboxing: &Letterboxing) -> impl Bundle {
                 RigidBody::Fixed,
                 Collider::cuboid(halfwidth, thickness),
                 CollisionGroups::new(Group::GROUP_1, Group::all()),
-                Restitution::coefficient(restitution),
+                Restitution {
+                    coefficient: restitution,
+                    combine_rule: CoefficientCombineRule::Max,
+                },
             ),
             (
                 Name::new("Bottom Wall"),
@@ -277,7 +464,10 @@ fn screen_bounds(letterboxing: &Letterboxing) -> impl Bundle {
                 RigidBody::Fixed,
                 Collider::cuboid(halfwidth, thickness),
                 CollisionGroups::new(Group::GROUP_1, Group::all()),
-                Restitution::coefficient(restitution),
+                Restitution {
+                    coefficient: restitution,
+                    combine_rule: CoefficientCombineRule::Max,
+                },
             )
         ],
     )
@@ -300,51 +490,93 @@ fn increase_particle_count(
 
 fn decrease_particle_count(
     mut events: EventReader<ParticleDespawned>,
-    mut level_query: Query<(Entity, &mut LevelState, &mut ParticleCount), With<RawLevel>>,
+    mut level_query: Query<(&LevelState, &mut ParticleCount)>,
+) {
+    let (level_state, mut particle_count) = level_query.single_mut().unwrap();
+    if *level_state == LevelState::Ended {
+        return;
+    }
+
+    for _ in events.read() {
+        particle_count.0 -= 1;
+    }
+}
+
+/// Ends the level once its [`objective::Objective`] is complete.
+fn complete_objective(
+    mut level_query: Query<(
+        Entity,
+        &RawLevel,
+        &mut LevelState,
+        &ParticleCount,
+        &stats::LevelStats,
+    )>,
+    particle_query: Query<(&Particle, Has<Killer>, Has<Invincible>)>,
     mut player_query: Query<&mut Player, Without<RawLevel>>,
+    level_config: Res<LevelConfig>,
     audio_assets: Res<LevelAudioAssets>,
     mut time_events: EventWriter<SetTimeScale>,
     mut time_override_events: EventWriter<SetTimeScaleOverride>,
     mut commands: Commands,
 ) {
-    let (level_entity, mut level_state, mut particle_count) = level_query.single_mut().unwrap();
+    let (level_entity, level, mut level_state, particle_count, stats) =
+        level_query.single_mut().unwrap();
     if *level_state == LevelState::Ended {
         return;
     }
 
-    for _ in events.read() {
-        particle_count.0 -= 1;
-    }
+    let progress = ObjectiveProgress {
+        particles: particle_count.0,
+        killers: particle_query
+            .iter()
+            .map(|(particle, killer, invincible)| {
+                // Invincible particles get their behavior, and with it `Killer`, later.
+                let killer = killer || (invincible && particle.kind == ParticleKind::Killer);
+                killers_left(particle, killer)
+            })
+            .sum(),
+        elapsed: stats.elapsed,
+        player_alive: !player_query.is_empty(),
+    };
 
-    if particle_count.0 == 0 {
+    if level.0.objective.is_complete(&progress) {
         commands.entity(level_entity).with_children(|parent| {
-            parent.spawn(EndLevelTimer::new());
+            parent.spawn(EndLevelTimer::new(level_config.end_level_delay));
             parent.spawn(sound_effect(audio_assets.level_completed_sound.clone()));
         });
 
         *level_state = LevelState::Ended;
 
         if let Ok(mut player) = player_query.single_mut() {
-            player.can_move = false;
+            player.state = PlayerState::Finished;
         }
 
-        time_override_events.write(SetTimeScaleOverride(None));
-        time_events.write(SetTimeScale(TimeScaleKind::Normal));
+        reset_time_scale(&mut time_events, &mut time_override_events);
     }
 }
 
+/// The killers a particle is or will split into, subparticles being counted by their kind.
+fn killers_left(particle: &Particle, killer: bool) -> usize {
+    let nested: usize = particle
+        .subparticles
+        .iter()
+        .map(|subparticle| killers_left(subparticle, subparticle.kind == ParticleKind::Killer))
+        .sum();
+    usize::from(killer) + nested
+}
+
 #[derive(Component)]
 struct EndLevelTimer(Timer);
 
 impl EndLevelTimer {
-    pub fn new() -> Self {
-        Self(Timer::new(Duration::from_secs_f32(2.0), TimerMode::Once))
+    pub fn new(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
     }
 }
 
 fn tick_end_level_timer(
     mut query: Query<(Entity, &mut EndLevelTimer)>,
-    time: Res<Time>,
+    time: Res<Time<Game>>,
     mut end_level_events: EventWriter<EndLevel>,
     mut commands: Commands,
 ) {
@@ -360,17 +592,21 @@ fn tick_end_level_timer(
 
 fn restart_level(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut level_query: Query<(Entity, &mut RawLevel, Option<&Level>)>,
+    key_bindings: Res<KeyBindings>,
+    level_query: Query<(Entity, &PristineLevel, Option<&Level>, &PlaySource)>,
     audio_assets: Res<LevelAudioAssets>,
     mut commands: Commands,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        let (entity, mut raw_level, level) = level_query.single_mut().unwrap();
+    if keyboard_input.just_pressed(key_bindings.restart) {
+        let (entity, pristine_level, level, source) = level_query.single().unwrap();
 
+        // Clone instead of taking the data, so the level can be restarted repeatedly
+        // even if the previous restart hasn't been applied yet.
         commands.entity(entity).despawn();
         commands.trigger(SpawnRawLevel {
-            data: std::mem::take(&mut raw_level.0),
+            data: pristine_level.0.clone(),
             level: level.cloned(),
+            source: *source,
         });
 
         commands.spawn((
@@ -386,45 +622,133 @@ struct EndLevel;
 
 fn end_level(
     mut events: EventReader<EndLevel>,
-    level_query: Query<(Entity, Option<&Level>), With<RawLevel>>,
+    level_query: Query<(Entity, Option<&Level>, &PlaySource), With<RawLevel>>,
     level_assets: Res<LevelAssets>,
+    mut run_progress: ResMut<run::RunProgress>,
     mut end_game_events: EventWriter<EndGame>,
     mut commands: Commands,
-    editor_state: Res<EditorState>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
     if !events.is_empty() {
-        let (entity, level) = level_query.single().unwrap();
-
-        let Some(level) = level else {
-            if editor_state.editing {
-                next_screen.set(Screen::Editor);
-            } else {
-                next_screen.set(Screen::Levels);
-            }
+        let (entity, level, source) = level_query.single().unwrap();
+        debug!(?level, ?source, "Level ended");
 
+        // Only plays that record progress go on through the run.
+        let Some(level) = level.filter(|_| source.records_progress()) else {
+            next_screen.set(source.return_screen());
             return;
         };
 
-        if let Level::Default(id) = level {
-            let new_id = id + 1;
+        if let Level::Default(_) = level {
+            let new_id = run_progress.current_default_index + 1;
 
             if level_assets.default.len() <= new_id {
                 end_game_events.write(EndGame);
                 return;
             }
+            run_progress.current_default_index = new_id;
 
             // Spawn next level.
-            commands.trigger(SpawnLevel(Level::Default(id + 1)));
+            commands.entity(entity).despawn();
+            commands.trigger(SpawnLevel(Level::Default(new_id)));
         } else {
             panic!("Not implemented.");
         }
-
-        commands.entity(entity).despawn();
     }
     events.clear();
 }
 
+#[cfg(feature = "dev")]
+const NEXT_LEVEL_KEY: KeyCode = KeyCode::KeyN;
+// `P` is already used for pausing.
+#[cfg(feature = "dev")]
+const PREVIOUS_LEVEL_KEY: KeyCode = KeyCode::KeyB;
+
+/// Jumps to the next or previous default level without completing the current one.
+#[cfg(feature = "dev")]
+fn skip_level(
+    input: Res<ButtonInput<KeyCode>>,
+    level_query: Query<(Entity, &Level), With<RawLevel>>,
+    level_assets: Res<LevelAssets>,
+    mut time_events: EventWriter<SetTimeScale>,
+    mut time_override_events: EventWriter<SetTimeScaleOverride>,
+    mut commands: Commands,
+) {
+    let offset = if input.just_pressed(NEXT_LEVEL_KEY) {
+        1
+    } else if input.just_pressed(PREVIOUS_LEVEL_KEY) {
+        -1
+    } else {
+        return;
+    };
+
+    let Ok((entity, &Level::Default(id))) = level_query.single() else {
+        return;
+    };
+
+    let new_id = id
+        .saturating_add_signed(offset)
+        .min(level_assets.default.len().saturating_sub(1));
+    if new_id == id {
+        return;
+    }
+
+    // The skipped level may have been left while aiming, or with the time scale overridden.
+    reset_time_scale(&mut time_events, &mut time_override_events);
+    commands.entity(entity).despawn();
+    commands.trigger(SpawnLevel(Level::Default(new_id)));
+}
+
+#[cfg(feature = "dev")]
+const SNAPSHOT_KEY: KeyCode = KeyCode::F9;
+
+/// Copies the level as it is being played to the clipboard as RON, for reproducing bug reports.
+#[cfg(feature = "dev")]
+fn copy_level_snapshot(
+    input: Res<ButtonInput<KeyCode>>,
+    level_query: Query<&RawLevel>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    particle_query: Query<
+        (&GlobalTransform, &Velocity, &Particle),
+        Without<crate::demo::particle::PooledParticle>,
+    >,
+    speed_scale: Res<crate::demo::particle::ParticleSpeedScale>,
+    mut contexts: bevy_inspector_egui::bevy_egui::EguiContexts,
+) {
+    if !input.just_pressed(SNAPSHOT_KEY) {
+        return;
+    }
+
+    let Ok(level) = level_query.single() else {
+        return;
+    };
+
+    let snapshot = level.0.snapshot(
+        player_query
+            .single()
+            .ok()
+            .map(|transform| transform.translation().xy()),
+        particle_query
+            .iter()
+            .map(|(transform, velocity, particle)| {
+                // Loading the snapshot scales the velocities again.
+                let velocity = Velocity {
+                    linvel: speed_scale.unscale(velocity.linvel),
+                    ..*velocity
+                };
+                (transform.translation().xy(), velocity, particle)
+            }),
+    );
+
+    match ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default()) {
+        Ok(string) => {
+            contexts.ctx_mut().copy_text(string);
+            info!("Copied a snapshot of the level to the clipboard.");
+        }
+        Err(error) => error!("Failed to serialize the level snapshot: {error}"),
+    }
+}
+
 #[derive(Event)]
 struct EndGame;
 
@@ -434,3 +758,477 @@ fn end_game(mut events: EventReader<EndGame>, mut next_screen: ResMut<NextState<
     }
     events.clear();
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, state::app::StatesPlugin};
+
+    use super::*;
+    use crate::demo::particle::{
+        self, Particle, ParticleSplitEvent, SpawnParticle, arrows::ArrowsAssets,
+    };
+
+    /// An app with just enough set up to spawn levels without a window or renderer.
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            crate::asset_tracking::plugin,
+        ));
+
+        app.init_asset::<Mesh>();
+        app.init_asset::<ColorMaterial>();
+        app.init_asset::<Image>();
+        app.init_asset::<AudioSource>();
+
+        // Only observers are exercised, so the plugin's systems never need to run.
+        app.add_plugins(particle::ParticlePlugin::default());
+        app.init_resource::<ArrowsAssets>();
+
+        app.init_resource::<PlayerConfig>();
+        app.init_resource::<Letterboxing>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.init_resource::<MusicAssets>();
+        app.init_resource::<LevelAudioAssets>();
+        app.add_plugins(StatesPlugin);
+        app.init_state::<Screen>();
+
+        app.add_observer(spawn_raw_level);
+
+        app
+    }
+
+    fn particle_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&Particle>()
+            .iter(app.world())
+            .count()
+    }
+
+    fn press_restart(app: &mut App) {
+        let restart = app.world().resource::<KeyBindings>().restart;
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release(restart);
+        input.clear();
+        input.press(restart);
+
+        app.world_mut().run_system_once(restart_level).unwrap();
+    }
+
+    #[test]
+    fn restart_twice_respawns_identical_level() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData::example(),
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        let initial_count = particle_count(&mut app);
+        assert_eq!(initial_count, LevelData::example().particles.len());
+
+        for _ in 0..2 {
+            press_restart(&mut app);
+            assert_eq!(particle_count(&mut app), initial_count);
+        }
+    }
+
+    #[test]
+    fn restart_after_split_respawns_original_level() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData::example(),
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        let initial_count = particle_count(&mut app);
+
+        // Split every particle of the level.
+        let particles: Vec<Entity> = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .iter(app.world())
+            .collect();
+        for particle in particles {
+            app.world_mut().send_event(ParticleSplitEvent(particle));
+        }
+        app.world_mut()
+            .run_system_once(particle::split_particle)
+            .unwrap();
+        assert_ne!(particle_count(&mut app), initial_count);
+
+        press_restart(&mut app);
+        assert_eq!(particle_count(&mut app), initial_count);
+
+        let mut query = app.world_mut().query::<&Particle>();
+        let respawned_subparticles: usize = query
+            .iter(app.world())
+            .map(|particle| particle.subparticles.len())
+            .sum();
+        let original_subparticles: usize = LevelData::example()
+            .particles
+            .iter()
+            .map(|particle_data| particle_data.particle.subparticles.len())
+            .sum();
+        assert_eq!(respawned_subparticles, original_subparticles);
+    }
+
+    #[test]
+    fn walls_use_the_level_restitution() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData {
+                wall_restitution: 0.9,
+                ..LevelData::example()
+            },
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        let mut query = app.world_mut().query::<(&Name, &Restitution)>();
+        let walls: Vec<_> = query
+            .iter(app.world())
+            .filter(|(name, _)| name.ends_with("Wall"))
+            .map(|(_, restitution)| restitution.coefficient)
+            .collect();
+        assert_eq!(walls, [0.9; 4]);
+    }
+
+    /// The horizontal speed of a particle after it bounced off a wall of `wall_restitution`,
+    /// stepping the physics.
+    fn wall_bounce_velocity(wall_restitution: f32) -> f32 {
+        let mut app = particle::physics_app();
+        app.world_mut()
+            .spawn(level_bounds(vec2(400.0, 400.0), wall_restitution));
+        app.world_mut().trigger(SpawnParticle {
+            translation: vec2(100.0, 0.0),
+            particle: Particle {
+                initial_velocity: vec2(BOUNCE_TEST_SPEED, 0.0),
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+
+        // The particle reaches the right wall after about 8 frames.
+        for _ in 0..30 {
+            app.update();
+        }
+
+        app.world_mut()
+            .query_filtered::<&Velocity, With<Particle>>()
+            .single(app.world())
+            .unwrap()
+            .linvel
+            .x
+    }
+
+    const BOUNCE_TEST_SPEED: f32 = 600.0;
+
+    #[test]
+    fn particles_bounce_off_walls_with_the_level_restitution() {
+        for restitution in [0.0, 0.5, 1.0] {
+            let velocity = wall_bounce_velocity(restitution);
+            assert!(
+                (velocity + restitution * BOUNCE_TEST_SPEED).abs() < 0.05 * BOUNCE_TEST_SPEED,
+                "restitution {restitution} bounced at {velocity}"
+            );
+        }
+    }
+
+    #[test]
+    fn spawning_a_level_replaces_the_previous_one() {
+        let mut app = test_app();
+
+        for _ in 0..2 {
+            app.world_mut().trigger(SpawnRawLevel {
+                data: LevelData::example(),
+                level: None,
+                source: PlaySource::Custom,
+            });
+            app.world_mut().flush();
+        }
+
+        let world = app.world_mut();
+        assert_eq!(world.query::<&RawLevel>().iter(world).count(), 1);
+        let bounds = world
+            .query::<&Name>()
+            .iter(world)
+            .filter(|name| name.as_str() == "Level Bounds")
+            .count();
+        assert_eq!(bounds, 1);
+        assert_eq!(
+            particle_count(&mut app),
+            LevelData::example().particles.len()
+        );
+    }
+
+    #[test]
+    fn a_level_without_particles_returns_to_the_menu() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData {
+                particles: Vec::new(),
+                ..LevelData::example()
+            },
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        assert!(matches!(
+            app.world().resource::<NextState<Screen>>(),
+            NextState::Pending(Screen::Levels)
+        ));
+
+        // Surviving needs no particles.
+        app.world_mut().resource_mut::<NextState<Screen>>().reset();
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData {
+                particles: Vec::new(),
+                objective: objective::Objective::Survive(Duration::from_secs(10)),
+                ..LevelData::example()
+            },
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        assert!(matches!(
+            app.world().resource::<NextState<Screen>>(),
+            NextState::Unchanged
+        ));
+    }
+
+    #[test]
+    fn completing_an_editor_test_play_records_no_progress() {
+        let mut app = test_app();
+        app.add_event::<EndLevel>();
+        app.add_event::<EndGame>();
+        app.add_event::<SetTimeScale>();
+        app.add_event::<SetTimeScaleOverride>();
+        app.init_resource::<Time<Game>>();
+        app.init_resource::<LevelConfig>();
+        app.init_resource::<stats::ComboConfig>();
+        app.add_plugins(run::plugin);
+        app.insert_resource(LevelAssets {
+            default: vec![Handle::default(); 3],
+            custom: default(),
+        });
+        app.world_mut()
+            .resource_mut::<run::RunProgress>()
+            .current_default_index = 2;
+
+        // Even a default level, which would otherwise go on through the run.
+        let mut data = LevelData::example();
+        for particle_data in &mut data.particles {
+            particle_data.particle.subparticles.clear();
+        }
+        app.world_mut().trigger(SpawnRawLevel {
+            data,
+            level: Some(Level::Default(0)),
+            source: PlaySource::EditorTest,
+        });
+        app.world_mut().flush();
+        app.world_mut()
+            .run_system_once(increase_particle_count)
+            .unwrap();
+
+        let particles: Vec<Entity> = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .iter(app.world())
+            .collect();
+        for particle in particles {
+            app.world_mut().send_event(ParticleSplitEvent(particle));
+        }
+        app.world_mut()
+            .run_system_once(particle::split_particle)
+            .unwrap();
+        app.world_mut()
+            .run_system_once(decrease_particle_count)
+            .unwrap();
+        app.world_mut()
+            .run_system_once(stats::update_level_stats)
+            .unwrap();
+        app.world_mut().run_system_once(complete_objective).unwrap();
+        let level_state = app
+            .world_mut()
+            .query::<&LevelState>()
+            .single(app.world())
+            .unwrap();
+        assert!(*level_state == LevelState::Ended);
+
+        app.world_mut().send_event(EndLevel);
+        app.world_mut().run_system_once(end_level).unwrap();
+
+        let stats = app
+            .world_mut()
+            .query::<&stats::LevelStats>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!((stats.score, stats.splits), (0, 0));
+        assert_eq!(
+            app.world()
+                .resource::<run::RunProgress>()
+                .current_default_index,
+            2
+        );
+        assert!(matches!(
+            app.world().resource::<NextState<Screen>>(),
+            NextState::Pending(Screen::Editor)
+        ));
+    }
+
+    #[test]
+    fn killers_nested_in_subparticles_keep_the_level_going() {
+        let mut app = test_app();
+        app.add_event::<SetTimeScale>();
+        app.add_event::<SetTimeScaleOverride>();
+        app.init_resource::<LevelConfig>();
+
+        let killer = Particle {
+            kind: ParticleKind::Killer,
+            initial_velocity: vec2(200.0, 0.0),
+            ..default()
+        };
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData {
+                particles: vec![level_data::ParticleData::new(
+                    Vec2::ZERO,
+                    Particle {
+                        subparticles: vec![killer],
+                        ..default()
+                    },
+                )],
+                objective: objective::Objective::DestroyKillers,
+                ..LevelData::example()
+            },
+            level: None,
+            source: PlaySource::Custom,
+        });
+        app.world_mut().flush();
+
+        let level_ended = |app: &mut App| {
+            app.world_mut().run_system_once(complete_objective).unwrap();
+            let world = app.world_mut();
+            *world.query::<&LevelState>().single(world).unwrap() == LevelState::Ended
+        };
+        assert!(!level_ended(&mut app));
+
+        // The killer is split out invincible, without its `Killer` component yet.
+        let particle = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut().send_event(ParticleSplitEvent(particle));
+        app.world_mut()
+            .run_system_once(particle::split_particle)
+            .unwrap();
+        assert!(!level_ended(&mut app));
+
+        particle::split_all_particles(app.world_mut()).unwrap();
+        assert!(level_ended(&mut app));
+    }
+
+    #[test]
+    fn leaving_gameplay_despawns_the_walls() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin));
+        app.init_asset::<Mesh>();
+        app.init_asset::<ColorMaterial>();
+        app.init_asset::<AudioSource>();
+        app.init_resource::<PlayerConfig>();
+        app.init_resource::<Letterboxing>();
+        app.init_resource::<MusicAssets>();
+        app.init_resource::<PopSounds>();
+        app.init_state::<Screen>();
+        app.add_observer(spawn_raw_level);
+
+        fn wall_count(app: &mut App) -> usize {
+            let world = app.world_mut();
+            world
+                .query::<&Name>()
+                .iter(world)
+                .filter(|name| name.ends_with("Wall"))
+                .count()
+        }
+
+        for _ in 0..2 {
+            app.world_mut()
+                .resource_mut::<NextState<Screen>>()
+                .set(Screen::Gameplay);
+            app.update();
+            // Without particles, so no particle plugin is needed.
+            app.world_mut().trigger(SpawnRawLevel {
+                data: LevelData {
+                    particles: Vec::new(),
+                    ..LevelData::example()
+                },
+                level: None,
+                source: PlaySource::Custom,
+            });
+            app.world_mut().flush();
+            assert_eq!(wall_count(&mut app), 4);
+
+            app.world_mut()
+                .resource_mut::<NextState<Screen>>()
+                .set(Screen::Title);
+            app.update();
+            assert_eq!(wall_count(&mut app), 0);
+        }
+    }
+
+    #[test]
+    fn completing_campaign_levels_advances_the_run() {
+        let mut app = test_app();
+        app.add_event::<EndLevel>();
+        app.add_event::<EndGame>();
+        app.init_resource::<run::RunProgress>();
+        app.insert_resource(LevelAssets {
+            default: vec![Handle::default(); 3],
+            custom: default(),
+        });
+
+        for expected in [1, 2] {
+            app.world_mut().spawn((
+                RawLevel(LevelData::default()),
+                Level::Default(expected - 1),
+                PlaySource::Campaign,
+            ));
+            app.world_mut().send_event(EndLevel);
+            app.world_mut().run_system_once(end_level).unwrap();
+
+            let progress = app.world().resource::<run::RunProgress>();
+            assert_eq!(progress.current_default_index, expected);
+        }
+
+        // The last level ends the game instead.
+        app.world_mut().spawn((
+            RawLevel(LevelData::default()),
+            Level::Default(2),
+            PlaySource::Campaign,
+        ));
+        app.world_mut().send_event(EndLevel);
+        app.world_mut().run_system_once(end_level).unwrap();
+        assert_eq!(
+            app.world()
+                .resource::<run::RunProgress>()
+                .current_default_index,
+            2
+        );
+        assert!(!app.world().resource::<Events<EndGame>>().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Moves the gameplay camera along with the player in levels larger than the screen.
+//!
+//! Gameplay only, the camera is panned by hand in the editor. The camera follows the player
+//! body, so the shake of the player visual stays visible on top of it.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    camera::{GameplayCamera, Letterboxing},
+    demo::player::Player,
+    screens::Screen,
+    settings::Settings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        follow_player
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), reset_camera);
+}
+
+/// Makes the camera follow the player while on this level, `size` being the walled area.
+#[derive(Component)]
+pub struct CameraFollow {
+    pub size: Vec2,
+}
+
+/// How quickly the camera catches up with the player.
+const FOLLOW_SPEED: f32 = 5.0;
+
+/// Where the camera should be to center `target` while showing nothing outside of a level of
+/// `level_size`, the camera is centered on axes where the level is smaller than the `screen`.
+pub fn clamp_camera(target: Vec2, level_size: Vec2, screen: Vec2) -> Vec2 {
+    let max = ((level_size - screen) / 2.0).max(Vec2::ZERO);
+    target.clamp(-max, max)
+}
+
+fn follow_player(
+    level_query: Query<&CameraFollow>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    mut camera: Single<&mut Transform, With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+) {
+    let target = match level_query.single() {
+        Ok(follow) => {
+            // Stay in place while there is no player, e.g. after it died.
+            let Ok(player) = player_query.single() else {
+                return;
+            };
+            let screen = vec2(
+                letterboxing.projection_size.width,
+                letterboxing.projection_size.height,
+            );
+            clamp_camera(player.translation().xy(), follow.size, screen)
+        }
+        // Levels without camera follow are shown from the origin.
+        Err(_) => Vec2::ZERO,
+    };
+
+    // Reduced motion keeps the camera on the target instead of gliding after it.
+    let factor = if settings.reduce_motion {
+        1.0
+    } else {
+        (FOLLOW_SPEED * time.delta_secs()).min(1.0)
+    };
+    let translation = camera.translation.xy().lerp(target, factor);
+    camera.translation = translation.extend(camera.translation.z);
+}
+
+fn reset_camera(mut camera: Single<&mut Transform, With<GameplayCamera>>) {
+    camera.translation = Vec3::ZERO.with_z(camera.translation.z);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_stays_within_the_level() {
+        let screen = vec2(200.0, 100.0);
+        let level_size = vec2(400.0, 80.0);
+
+        assert_eq!(
+            clamp_camera(vec2(50.0, 30.0), level_size, screen),
+            vec2(50.0, 0.0)
+        );
+        assert_eq!(
+            clamp_camera(vec2(-500.0, 0.0), level_size, screen),
+            vec2(-100.0, 0.0)
+        );
+    }
+}
@@ -1,14 +1,17 @@
 #![allow(unused)]
 
+use std::time::Duration;
+
 use bevy::{
     asset::{AssetLoader, LoadContext, RenderAssetUsages, io::Reader},
     prelude::*,
     render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
 };
-use bevy_rapier2d::prelude::Collider;
+use bevy_rapier2d::prelude::{Collider, Velocity};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::{objective::Objective, trigger::TriggerData};
 use crate::demo::particle::{Particle, ParticleKind};
 
 pub(super) fn plugin(app: &mut App) {
@@ -70,6 +73,9 @@ impl FlatColorMesh {
 pub struct ParticleData {
     pub spawn_position: Vec2,
     pub particle: Particle,
+    /// Shown in the editor to tell objects apart, unused in gameplay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 impl ParticleData {
@@ -77,24 +83,44 @@ impl ParticleData {
         Self {
             spawn_position,
             particle,
+            label: None,
         }
     }
 
     pub fn default_at(translation: Vec2) -> Self {
-        Self {
-            spawn_position: translation,
-            particle: Particle::default(),
-        }
+        Self::new(translation, Particle::default())
     }
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+/// The alpha trigger zones are drawn with.
+const TRIGGER_ZONE_ALPHA: f32 = 0.3;
+
+/// The outline of an obstacle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ObstacleShape {
+    /// Sized by [`ObstacleData::width`] and [`ObstacleData::height`], the only shape of older levels.
+    #[default]
+    Rectangle,
+    Circle {
+        radius: f32,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ObstacleData {
     pub transform: Transform,
     pub color: Color,
     pub width: f32,
     pub height: f32,
+    #[serde(default)]
+    pub shape: ObstacleShape,
     pub is_killer: bool,
+    /// Shown in the editor to tell objects apart, unused in gameplay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Makes the obstacle a trigger zone, nothing bounces off it then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<TriggerData>,
 }
 
 impl ObstacleData {
@@ -104,7 +130,19 @@ impl ObstacleData {
             color,
             width,
             height,
+            shape: ObstacleShape::Rectangle,
             is_killer: killer,
+            label: None,
+            trigger: None,
+        }
+    }
+
+    /// Trigger zones are see-through, so they don't look like walls.
+    pub fn display_color(&self) -> Color {
+        if self.trigger.is_some() {
+            self.color.with_alpha(TRIGGER_ZONE_ALPHA)
+        } else {
+            self.color
         }
     }
 
@@ -117,20 +155,352 @@ impl ObstacleData {
 
         Self::new(transform, color, width, height, killer)
     }
+
+    pub fn mesh(&self) -> Mesh {
+        match self.shape {
+            ObstacleShape::Rectangle => Rectangle::new(self.width, self.height).into(),
+            ObstacleShape::Circle { radius } => Circle::new(radius).into(),
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self.shape {
+            ObstacleShape::Rectangle => Collider::cuboid(self.width / 2.0, self.height / 2.0),
+            ObstacleShape::Circle { radius } => Collider::ball(radius),
+        }
+    }
+
+    /// Whether `point` in world space is inside the obstacle.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let local = self
+            .transform
+            .compute_affine()
+            .inverse()
+            .transform_point3(point.extend(0.0))
+            .xy();
+
+        match self.shape {
+            ObstacleShape::Rectangle => {
+                local.abs().cmple(vec2(self.width, self.height) / 2.0).all()
+            }
+            ObstacleShape::Circle { radius } => local.length() <= radius,
+        }
+    }
+
+    /// The corners of the obstacle in world space, those of the axis aligned bounding square
+    /// for a circle.
+    pub fn corners(&self) -> [Vec2; 4] {
+        let (half_size, transform) = match self.shape {
+            ObstacleShape::Rectangle => (vec2(self.width, self.height) / 2.0, self.transform),
+            ObstacleShape::Circle { radius } => (
+                Vec2::splat(radius),
+                self.transform.with_rotation(Quat::IDENTITY),
+            ),
+        };
+
+        [
+            vec2(-1.0, -1.0),
+            vec2(1.0, -1.0),
+            vec2(1.0, 1.0),
+            vec2(-1.0, 1.0),
+        ]
+        .map(|sign| {
+            transform
+                .transform_point((sign * half_size).extend(0.0))
+                .xy()
+        })
+    }
+}
+
+/// A single object of a level, copied on its own in the editor.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ObjectData {
+    Particle(ParticleData),
+    Obstacle(ObstacleData),
+}
+
+impl ObjectData {
+    pub fn translate(&mut self, offset: Vec2) {
+        match self {
+            Self::Particle(particle_data) => particle_data.spawn_position += offset,
+            Self::Obstacle(obstacle_data) => {
+                obstacle_data.transform.translation += offset.extend(0.0)
+            }
+        }
+    }
+}
+
+/// A likely mistake in a level, found by [`LevelData::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelWarning {
+    /// The player spawns outside of the screen, where it is never seen.
+    PlayerSpawnOutOfBounds,
+    /// Two obstacles covering the same area, as indices into [`LevelData::obstacles`].
+    DuplicateObstacles(usize, usize),
+    /// Two particles spawning at the same position, as indices into [`LevelData::particles`].
+    DuplicateParticles(usize, usize),
+}
+
+impl std::fmt::Display for LevelWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PlayerSpawnOutOfBounds => write!(f, "The player spawns outside of the screen"),
+            Self::DuplicateObstacles(a, b) => write!(f, "Obstacles {a} and {b} are duplicates"),
+            Self::DuplicateParticles(a, b) => write!(f, "Particles {a} and {b} are duplicates"),
+        }
+    }
 }
 
-#[derive(Asset, TypePath, Clone, Serialize, Deserialize, Default)]
+/// The version of the level format written by this build, see [`LevelData::migrate`].
+pub const LEVEL_DATA_VERSION: u32 = 1;
+
+/// A level as stored in a `.ron` file.
+///
+/// Fields added after the first version should be `#[serde(default)]`, so older files keep loading.
+#[derive(Asset, TypePath, Clone, Serialize, Deserialize)]
 pub struct LevelData {
+    /// Files written before versioning was introduced have version 0.
+    #[serde(default)]
+    pub version: u32,
     pub name: String,
+    #[serde(default)]
     pub author: Option<String>,
+    #[serde(default)]
     pub particles: Vec<ParticleData>,
+    #[serde(default)]
     pub obstacles: Vec<ObstacleData>,
+    #[serde(default)]
     pub player_spawn: Vec2,
+    /// Whether the arrows showing where subparticles go are shown in gameplay,
+    /// the editor always shows them.
+    #[serde(default = "show_arrows_default")]
+    pub show_arrows: bool,
+    /// Size of the walled area, centered on the origin, the size of the screen if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vec2>,
+    /// Whether the camera follows the player, for levels larger than the screen.
+    #[serde(default)]
+    pub camera_follow: bool,
+    /// How bouncy the walls are, particles and the player leave the bouncing to them.
+    #[serde(default = "wall_restitution_default")]
+    pub wall_restitution: f32,
+    /// Game time until the player dies, no limit if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<Duration>,
+    #[serde(default)]
+    pub objective: Objective,
+    /// Whether splitting particles pops, levels with huge chain reactions may turn it off.
+    #[serde(default = "pop_sounds_default")]
+    pub pop_sounds: bool,
+}
+
+fn show_arrows_default() -> bool {
+    true
+}
+
+fn wall_restitution_default() -> f32 {
+    0.5
+}
+
+fn pop_sounds_default() -> bool {
+    true
+}
+
+impl Default for LevelData {
+    fn default() -> Self {
+        Self {
+            version: LEVEL_DATA_VERSION,
+            name: String::new(),
+            author: None,
+            particles: Vec::new(),
+            obstacles: Vec::new(),
+            player_spawn: Vec2::ZERO,
+            show_arrows: show_arrows_default(),
+            size: None,
+            camera_follow: false,
+            wall_restitution: wall_restitution_default(),
+            time_limit: None,
+            objective: Objective::default(),
+            pop_sounds: pop_sounds_default(),
+        }
+    }
 }
 
 impl LevelData {
+    /// Upgrades level data loaded from an older version of the format to [`LEVEL_DATA_VERSION`].
+    pub fn migrate(&mut self) {
+        // Version 0 only lacks the version field, so there is nothing to upgrade yet.
+        // Future format changes add their upgrade steps here, in order.
+        self.version = LEVEL_DATA_VERSION;
+    }
+
+    /// Parses level data from RON and migrates it to the current version.
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        let mut level_data = ron::de::from_bytes::<LevelData>(bytes)?;
+        level_data.migrate();
+
+        Ok(level_data)
+    }
+
+    /// Mirrors the whole level, `scale` should be `(-1, 1)` to mirror it along the x-axis
+    /// or `(1, -1)` to mirror it along the y-axis.
+    pub fn mirror(&mut self, scale: Vec2) {
+        self.player_spawn *= scale;
+
+        for particle_data in &mut self.particles {
+            particle_data.spawn_position *= scale;
+            particle_data
+                .particle
+                .map_velocities(&|velocity| velocity * scale);
+            particle_data.particle.reverse_angvels();
+        }
+
+        for obstacle_data in &mut self.obstacles {
+            obstacle_data.transform.translation *= scale.extend(1.0);
+            // Obstacles are symmetric rectangles or circles, so mirroring them only flips their
+            // rotation.
+            obstacle_data.transform.rotation = obstacle_data.transform.rotation.inverse();
+        }
+    }
+
+    /// Rotates the whole level about the origin.
+    pub fn rotate(&mut self, rotation: Rot2) {
+        self.player_spawn = rotation * self.player_spawn;
+
+        for particle_data in &mut self.particles {
+            particle_data.spawn_position = rotation * particle_data.spawn_position;
+            particle_data
+                .particle
+                .map_velocities(&|velocity| rotation * velocity);
+        }
+
+        for obstacle_data in &mut self.obstacles {
+            let transform = &mut obstacle_data.transform;
+            transform.translation =
+                (rotation * transform.translation.xy()).extend(transform.translation.z);
+            transform.rotate_z(rotation.as_radians());
+        }
+    }
+
+    /// This level with the player and the particles where they are while it is being played.
+    ///
+    /// `particles` are the position, the velocity and the particle of each particle in play. The
+    /// velocities are taken as authored, unscale them first, see
+    /// [`crate::demo::particle::ParticleSpeedScale::unscale`].
+    pub fn snapshot<'a>(
+        &self,
+        player: Option<Vec2>,
+        particles: impl IntoIterator<Item = (Vec2, Velocity, &'a Particle)>,
+    ) -> Self {
+        Self {
+            name: format!("{} (snapshot)", self.name),
+            player_spawn: player.unwrap_or(self.player_spawn),
+            particles: particles
+                .into_iter()
+                .map(|(position, velocity, particle)| {
+                    ParticleData::new(
+                        position,
+                        Particle {
+                            initial_velocity: velocity.linvel,
+                            initial_angvel: velocity.angvel,
+                            ..particle.clone()
+                        },
+                    )
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Finds likely mistakes in the level, see [`LevelWarning`].
+    ///
+    /// `screen` is the size of the screen, centered on the origin.
+    pub fn validate(&self, screen: Vec2, duplicate_tolerance: f32) -> Vec<LevelWarning> {
+        let mut warnings = Vec::new();
+
+        if !self.player_spawn_in_bounds(screen) {
+            warnings.push(LevelWarning::PlayerSpawnOutOfBounds);
+        }
+        warnings.extend(self.find_duplicates(duplicate_tolerance));
+
+        warnings
+    }
+
+    /// The size of the walled area, `screen` being the size of the screen.
+    pub fn bounds(&self, screen: Vec2) -> Vec2 {
+        self.size.unwrap_or(screen)
+    }
+
+    /// The size of the area the player can be seen in, the camera only moves when following.
+    pub fn visible_bounds(&self, screen: Vec2) -> Vec2 {
+        if self.camera_follow {
+            self.bounds(screen)
+        } else {
+            self.bounds(screen).min(screen)
+        }
+    }
+
+    pub fn player_spawn_in_bounds(&self, screen: Vec2) -> bool {
+        self.player_spawn
+            .abs()
+            .cmple(self.visible_bounds(screen) / 2.0)
+            .all()
+    }
+
+    /// The smallest rectangle containing every obstacle, particle and the player spawn.
+    ///
+    /// Rotated obstacles are covered by the bounds of their corners.
+    pub fn bounding_box(&self) -> Rect {
+        let mut rect = Rect::from_center_size(self.player_spawn, Vec2::ZERO);
+
+        for obstacle_data in &self.obstacles {
+            for corner in obstacle_data.corners() {
+                rect = rect.union_point(corner);
+            }
+        }
+        for particle_data in &self.particles {
+            rect = rect.union(Rect::from_center_half_size(
+                particle_data.spawn_position,
+                Vec2::splat(particle_data.particle.radius),
+            ));
+        }
+
+        rect
+    }
+
+    /// Finds obstacles and particles placed on top of an identical one, up to `tolerance`
+    /// world units.
+    pub fn find_duplicates(&self, tolerance: f32) -> Vec<LevelWarning> {
+        let mut warnings = Vec::new();
+
+        let corners: Vec<_> = self.obstacles.iter().map(ObstacleData::corners).collect();
+        for (i, a) in corners.iter().enumerate() {
+            for (j, b) in corners.iter().enumerate().skip(i + 1) {
+                // Compared as sets, an obstacle rotated by 180° covers the same area.
+                let duplicate = a
+                    .iter()
+                    .all(|a| b.iter().any(|b| a.distance(*b) <= tolerance));
+
+                if duplicate {
+                    warnings.push(LevelWarning::DuplicateObstacles(i, j));
+                }
+            }
+        }
+
+        for (i, a) in self.particles.iter().enumerate() {
+            for (j, b) in self.particles.iter().enumerate().skip(i + 1) {
+                if a.spawn_position.distance(b.spawn_position) <= tolerance {
+                    warnings.push(LevelWarning::DuplicateParticles(i, j));
+                }
+            }
+        }
+
+        warnings
+    }
+
     pub fn example() -> Self {
         Self {
+            version: LEVEL_DATA_VERSION,
             name: String::from("Example"),
             author: None,
             particles: vec![
@@ -188,6 +558,13 @@ impl LevelData {
                 false,
             )],
             player_spawn: vec2(0.0, 0.0),
+            show_arrows: true,
+            size: None,
+            camera_follow: false,
+            wall_restitution: 0.5,
+            time_limit: None,
+            objective: Objective::ClearParticles,
+            pop_sounds: true,
         }
     }
 }
@@ -218,12 +595,252 @@ impl AssetLoader for LevelDataLoader {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
 
-        let level_data = ron::de::from_bytes::<LevelData>(&bytes)?;
-
-        Ok(level_data)
+        Ok(LevelData::from_ron(&bytes)?)
     }
 
     fn extensions(&self) -> &[&str] {
         &["ron"]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_keep_the_live_particles() {
+        let level = LevelData {
+            name: String::from("Level"),
+            player_spawn: vec2(10.0, 0.0),
+            ..LevelData::example()
+        };
+        let particle = Particle {
+            kind: ParticleKind::Killer,
+            ..default()
+        };
+
+        let snapshot = level.snapshot(
+            Some(vec2(-5.0, 5.0)),
+            [(
+                vec2(1.0, 2.0),
+                Velocity {
+                    linvel: vec2(30.0, 0.0),
+                    angvel: 1.5,
+                },
+                &particle,
+            )],
+        );
+        assert_eq!(snapshot.name, "Level (snapshot)");
+        assert_eq!(snapshot.player_spawn, vec2(-5.0, 5.0));
+        assert_eq!(snapshot.particles.len(), 1);
+        assert_eq!(snapshot.particles[0].spawn_position, vec2(1.0, 2.0));
+        assert_eq!(
+            snapshot.particles[0].particle.initial_velocity,
+            vec2(30.0, 0.0)
+        );
+        assert_eq!(snapshot.particles[0].particle.initial_angvel, 1.5);
+        assert_eq!(snapshot.particles[0].particle.kind, ParticleKind::Killer);
+        assert_eq!(snapshot.obstacles.len(), level.obstacles.len());
+
+        // The player may have died.
+        assert_eq!(level.snapshot(None, []).player_spawn, vec2(10.0, 0.0));
+    }
+
+    #[test]
+    fn bounding_box_covers_every_object() {
+        let mut level = LevelData {
+            player_spawn: vec2(-200.0, 10.0),
+            ..default()
+        };
+        assert_eq!(level.bounding_box(), Rect::new(-200.0, 10.0, -200.0, 10.0));
+
+        level
+            .particles
+            .push(ParticleData::default_at(vec2(0.0, 100.0)));
+        let radius = level.particles[0].particle.radius;
+        assert_eq!(
+            level.bounding_box(),
+            Rect::new(-200.0, 10.0, radius, 100.0 + radius)
+        );
+
+        // Rotated by 90°, a wide obstacle becomes a tall one.
+        level.particles.clear();
+        let mut obstacle_data = ObstacleData::default_at(vec2(300.0, 0.0));
+        obstacle_data.width = 100.0;
+        obstacle_data.height = 20.0;
+        obstacle_data
+            .transform
+            .rotate_z(std::f32::consts::FRAC_PI_2);
+        level.obstacles.push(obstacle_data);
+
+        let bounding_box = level.bounding_box();
+        assert!(bounding_box.min.abs_diff_eq(vec2(-200.0, -50.0), 1e-3));
+        assert!(bounding_box.max.abs_diff_eq(vec2(310.0, 50.0), 1e-3));
+    }
+
+    #[test]
+    fn mirror_flips_subparticle_velocities() {
+        let mut level = LevelData::example();
+        level.mirror(vec2(-1.0, 1.0));
+
+        assert_eq!(level.particles[0].spawn_position, vec2(100.0, 0.0));
+        assert_eq!(
+            level.particles[0].particle.subparticles[0].subparticles[0].initial_velocity,
+            vec2(-200.0, 0.0)
+        );
+        assert_eq!(
+            level.obstacles[0].transform.translation,
+            vec3(-100.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn rotating_four_times_restores_level() {
+        let mut original = LevelData::example();
+        original.obstacles[0].transform.rotate_z(0.3);
+        let mut level = original.clone();
+
+        for _ in 0..4 {
+            level.rotate(Rot2::degrees(90.0));
+        }
+
+        assert!(level.player_spawn.abs_diff_eq(original.player_spawn, 1e-3));
+        for (particle_data, original) in level.particles.iter().zip(&original.particles) {
+            assert!(
+                particle_data
+                    .spawn_position
+                    .abs_diff_eq(original.spawn_position, 1e-3)
+            );
+            assert!(
+                particle_data.particle.subparticles[0]
+                    .initial_velocity
+                    .abs_diff_eq(original.particle.subparticles[0].initial_velocity, 1e-3)
+            );
+        }
+
+        let transform = level.obstacles[0].transform;
+        let original_transform = original.obstacles[0].transform;
+        assert!(
+            transform
+                .translation
+                .abs_diff_eq(original_transform.translation, 1e-3)
+        );
+        assert!(
+            transform
+                .rotation
+                .angle_between(original_transform.rotation)
+                < 1e-3
+        );
+    }
+
+    #[test]
+    fn old_level_loads_with_defaults() {
+        let ron = r#"(
+            name: "Old",
+            particles: [
+                (
+                    spawn_position: (10.0, 20.0),
+                    particle: (radius: 30.0),
+                ),
+            ],
+        )"#;
+
+        let level = LevelData::from_ron(ron.as_bytes()).unwrap();
+
+        assert_eq!(level.version, LEVEL_DATA_VERSION);
+        assert_eq!(level.name, "Old");
+        assert!(level.author.is_none());
+        assert!(level.obstacles.is_empty());
+        assert_eq!(level.player_spawn, Vec2::ZERO);
+
+        let particle = &level.particles[0].particle;
+        assert_eq!(particle.radius, 30.0);
+        assert_eq!(particle.kind, ParticleKind::Normal);
+        assert!(particle.subparticles.is_empty());
+    }
+
+    #[test]
+    fn copied_object_is_not_a_level() {
+        let object = ObjectData::Obstacle(ObstacleData::default_at(vec2(10.0, 20.0)));
+        let ron = ron::ser::to_string(&object).unwrap();
+
+        assert!(LevelData::from_ron(ron.as_bytes()).is_err());
+        let Ok(ObjectData::Obstacle(obstacle_data)) = ron::de::from_str(&ron) else {
+            panic!("expected an obstacle");
+        };
+        assert_eq!(obstacle_data.transform.translation, vec3(10.0, 20.0, 0.0));
+    }
+
+    #[test]
+    fn obstacles_without_a_shape_are_rectangles() {
+        let ron = ron::to_string(&ObstacleData::default_at(Vec2::ZERO)).unwrap();
+        // As saved before obstacles had shapes.
+        let old_ron = ron.replace("shape:Rectangle,", "");
+        assert_ne!(ron, old_ron);
+
+        let obstacle_data: ObstacleData = ron::de::from_str(&old_ron).unwrap();
+        assert_eq!(obstacle_data.shape, ObstacleShape::Rectangle);
+        assert!(obstacle_data.contains(vec2(24.0, 24.0)));
+    }
+
+    #[test]
+    fn circles_are_hit_inside_their_radius() {
+        let mut obstacle_data = ObstacleData::default_at(vec2(100.0, 0.0));
+        obstacle_data.shape = ObstacleShape::Circle { radius: 20.0 };
+
+        assert!(obstacle_data.contains(vec2(110.0, 10.0)));
+        assert!(!obstacle_data.contains(vec2(118.0, 18.0)));
+        assert_eq!(obstacle_data.corners()[0], vec2(80.0, -20.0));
+    }
+
+    #[test]
+    fn labels_round_trip_and_default_to_none() {
+        let mut level = LevelData::example();
+        level.obstacles[0].label = Some(String::from("Wall"));
+
+        let ron = ron::to_string(&level).unwrap();
+        let level = LevelData::from_ron(ron.as_bytes()).unwrap();
+
+        assert_eq!(level.obstacles[0].label.as_deref(), Some("Wall"));
+        assert_eq!(level.particles[0].label, None);
+    }
+
+    #[test]
+    fn validate_flags_player_spawn_outside_of_bounds() {
+        let bounds = vec2(1280.0, 720.0);
+        let mut level = LevelData::example();
+        assert!(level.validate(bounds, 1.0).is_empty());
+
+        level.player_spawn = vec2(0.0, 400.0);
+        assert_eq!(
+            level.validate(bounds, 1.0),
+            [LevelWarning::PlayerSpawnOutOfBounds]
+        );
+    }
+
+    #[test]
+    fn finds_duplicate_obstacles_and_particles() {
+        let mut level = LevelData::example();
+        assert!(level.find_duplicates(1.0).is_empty());
+
+        let mut flipped = level.obstacles[0].clone();
+        flipped.transform.translation.x += 0.5;
+        flipped.transform.rotate_z(std::f32::consts::PI);
+        level.obstacles.push(flipped);
+        level
+            .particles
+            .push(ParticleData::default_at(vec2(-100.0, 0.0)));
+
+        assert_eq!(
+            level.find_duplicates(1.0),
+            vec![
+                LevelWarning::DuplicateObstacles(0, 1),
+                LevelWarning::DuplicateParticles(0, 2),
+            ]
+        );
+        assert_eq!(
+            level.find_duplicates(0.1),
+            vec![LevelWarning::DuplicateParticles(0, 2)]
+        );
+    }
+}
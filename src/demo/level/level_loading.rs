@@ -1,6 +1,14 @@
+#[cfg(not(target_family = "wasm"))]
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::asset::io::AssetSourceBuilder;
 use bevy::{platform::collections::HashMap, prelude::*};
 
-use crate::{asset_tracking::LoadResource, demo::level::level_data::LevelData};
+use crate::{
+    asset_tracking::LoadResource,
+    demo::level::{Level, level_data::LevelData},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<LevelAssets>();
@@ -8,6 +16,66 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, initialize_level_assets);
 }
 
+/// Environment variable pointing to an external levels directory.
+#[cfg(not(target_family = "wasm"))]
+const LEVELS_DIR_VAR: &str = "ANTIM4TTER_LEVELS_DIR";
+
+/// Command-line argument pointing to an external levels directory, takes precedence over [`LEVELS_DIR_VAR`].
+#[cfg(not(target_family = "wasm"))]
+const LEVELS_DIR_ARG: &str = "--levels-dir";
+
+/// The asset source name of the external levels directory.
+#[cfg(not(target_family = "wasm"))]
+const LEVELS_DIR_SOURCE: &str = "levels";
+
+/// An external levels directory with the same layout as `assets/levels`,
+/// levels are loaded from it instead of the bundled ones.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource)]
+struct LevelsDir(PathBuf);
+
+/// Registers the external levels directory, if one is set, as an asset source.
+///
+/// Must be called before the `AssetPlugin` is added.
+#[cfg(not(target_family = "wasm"))]
+pub fn register_levels_dir(app: &mut App) {
+    let path = std::env::args()
+        .skip_while(|arg| arg != LEVELS_DIR_ARG)
+        .nth(1)
+        .or_else(|| std::env::var(LEVELS_DIR_VAR).ok());
+    let Some(path) = path else {
+        return;
+    };
+    let path = PathBuf::from(path);
+
+    // Missing directories are reported once logging is set up, in `LevelHandles::from_world`.
+    if path.is_dir() {
+        app.register_asset_source(
+            LEVELS_DIR_SOURCE,
+            AssetSourceBuilder::platform_default(&path.to_string_lossy(), None),
+        );
+    }
+    app.insert_resource(LevelsDir(path));
+}
+
+/// Returns the asset paths of all levels in the given folder of the external levels directory.
+#[cfg(not(target_family = "wasm"))]
+fn scan_levels_dir(levels_dir: &Path, folder: &str) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(levels_dir.join(folder)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ron"))
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?;
+            Some(format!("{LEVELS_DIR_SOURCE}://{folder}/{file_name}"))
+        })
+        .collect()
+}
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LevelHandles {
@@ -19,6 +87,29 @@ pub struct LevelHandles {
 
 impl FromWorld for LevelHandles {
     fn from_world(world: &mut World) -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(LevelsDir(levels_dir)) = world.get_resource::<LevelsDir>() {
+            if levels_dir.is_dir() {
+                let assets = world.resource::<AssetServer>();
+                let load_all = |folder| {
+                    scan_levels_dir(levels_dir, folder)
+                        .into_iter()
+                        .map(|path| assets.load(path))
+                        .collect()
+                };
+
+                return Self {
+                    default: load_all("default"),
+                    custom: load_all("custom"),
+                };
+            }
+
+            warn!(
+                "Levels directory {} does not exist, falling back to the bundled levels.",
+                levels_dir.display()
+            );
+        }
+
         let default_levels: Vec<&'static str> =
             vec!["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"];
         let custom_levels: Vec<&'static str> = vec![];
@@ -52,6 +143,81 @@ pub struct LevelAssets {
     pub custom: HashMap<String, Handle<LevelData>>,
 }
 
+/// A level with the metadata shown when browsing levels, see [`LevelAssets::all_levels`].
+#[derive(Clone, Debug)]
+pub struct LevelInfo {
+    pub level: Level,
+    pub handle: Handle<LevelData>,
+    pub name: String,
+    pub author: Option<String>,
+    /// Whether the level data is loaded, levels that failed to load can't be played.
+    pub loaded: bool,
+}
+
+impl LevelAssets {
+    /// All levels, the default ones in order followed by the custom ones sorted by name.
+    ///
+    /// Levels that aren't loaded are listed too, with their [`Level::fallback_name`], so the
+    /// levels after them keep their place.
+    pub fn all_levels(&self, levels: &Assets<LevelData>) -> Vec<LevelInfo> {
+        let default = self
+            .default
+            .iter()
+            .enumerate()
+            .map(|(id, handle)| (Level::Default(id), handle));
+
+        let mut custom: Vec<_> = self.custom.iter().collect();
+        custom.sort_by_key(|(name, _)| *name);
+        let custom = custom
+            .into_iter()
+            .map(|(name, handle)| (Level::Custom(name.clone()), handle));
+
+        default
+            .chain(custom)
+            .map(|(level, handle)| {
+                let level_data = levels.get(handle);
+
+                LevelInfo {
+                    name: level_data.map_or_else(
+                        || level.fallback_name(),
+                        |level_data| level_data.name.clone(),
+                    ),
+                    author: level_data.and_then(|level_data| level_data.author.clone()),
+                    loaded: level_data.is_some(),
+                    level,
+                    handle: handle.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Maps custom levels by their names.
+///
+/// Levels whose name is already taken are renamed by appending a number instead of being dropped.
+fn map_custom_levels(
+    levels: impl IntoIterator<Item = (String, Handle<LevelData>)>,
+) -> HashMap<String, Handle<LevelData>> {
+    let mut map = HashMap::new();
+
+    for (name, handle) in levels {
+        let mut unique_name = name.clone();
+        let mut suffix = 2;
+        while map.contains_key(&unique_name) {
+            unique_name = format!("{name} ({suffix})");
+            suffix += 1;
+        }
+
+        if unique_name != name {
+            warn!("There are multiple custom levels named {name}, renaming one to {unique_name}.");
+        }
+
+        map.insert(unique_name, handle);
+    }
+
+    map
+}
+
 // Initializes the LevelAssets resource from the raw LevelHandles resource.
 fn initialize_level_assets(
     mut events: EventReader<AssetEvent<LevelHandles>>,
@@ -64,7 +230,7 @@ fn initialize_level_assets(
             let level_handles = level_handles_assets.get_mut(*id).unwrap();
 
             let default = std::mem::take(&mut level_handles.default);
-            let custom = std::mem::take(&mut level_handles.default);
+            let custom = std::mem::take(&mut level_handles.custom);
 
             // Load default levels as a sorted Vec<Handle<LevelData>>.
             let map_default = |handles: Vec<Handle<LevelData>>| {
@@ -94,14 +260,11 @@ fn initialize_level_assets(
 
             // Load custom levels as a HashMap<String, Handle<LevelData>>.
             let map_custom = |handles: Vec<Handle<LevelData>>| {
-                handles
-                    .iter()
-                    .map(|h| {
-                        let level = levels.get(h).unwrap();
-
-                        (level.name.clone(), h.clone())
-                    })
-                    .collect()
+                map_custom_levels(handles.into_iter().map(|h| {
+                    let level = levels.get(&h).unwrap();
+
+                    (level.name.clone(), h)
+                }))
             };
 
             commands.remove_resource::<LevelHandles>();
@@ -112,3 +275,70 @@ fn initialize_level_assets(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::weak_handle;
+
+    use super::*;
+
+    #[test]
+    fn custom_levels_with_the_same_name_are_kept() {
+        let first = weak_handle!("3a6e4e0c-5e8a-4c31-9f61-0d5b8c2f7a11");
+        let second = weak_handle!("b7d2f9a4-1c3e-4f57-8a90-6e2d4b1c9f22");
+
+        let custom = map_custom_levels([
+            (String::from("Level"), first.clone()),
+            (String::from("Level"), second.clone()),
+        ]);
+
+        assert_eq!(custom.len(), 2);
+        assert_eq!(custom["Level"], first);
+        assert_eq!(custom["Level (2)"], second);
+    }
+
+    #[test]
+    fn all_levels_lists_default_levels_first() {
+        let mut levels = Assets::<LevelData>::default();
+        let mut add = |name: &str| {
+            levels.add(LevelData {
+                name: String::from(name),
+                ..default()
+            })
+        };
+        let level_assets = LevelAssets {
+            default: vec![add("1"), add("2")],
+            custom: HashMap::from_iter([
+                (String::from("b"), add("b")),
+                (String::from("a"), add("a")),
+            ]),
+        };
+
+        let all_levels = level_assets.all_levels(&levels);
+
+        let names: Vec<_> = all_levels.iter().map(|info| info.name.as_str()).collect();
+        assert_eq!(names, ["1", "2", "a", "b"]);
+        assert_eq!(all_levels[1].level, Level::Default(1));
+        assert_eq!(all_levels[2].level, Level::Custom(String::from("a")));
+    }
+
+    #[test]
+    fn all_levels_keeps_the_place_of_unloaded_levels() {
+        let mut levels = Assets::<LevelData>::default();
+        let level_assets = LevelAssets {
+            default: vec![
+                levels.add(LevelData::default()),
+                Handle::default(),
+                levels.add(LevelData::default()),
+            ],
+            custom: HashMap::new(),
+        };
+
+        let all_levels = level_assets.all_levels(&levels);
+
+        assert_eq!(all_levels.len(), 3);
+        assert!(!all_levels[1].loaded);
+        assert_eq!(all_levels[1].name, "Level 2");
+        assert_eq!(all_levels[2].level, Level::Default(2));
+    }
+}
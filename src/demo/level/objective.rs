@@ -0,0 +1,129 @@
+//! What a level asks of the player to be completed, shown on the HUD.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, ui::Val::*};
+use serde::{Deserialize, Serialize};
+
+use super::{RawLevel, stats::LevelStats};
+use crate::{AppSystems, screens::Screen, theme::widget};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_objective_label);
+    app.add_systems(
+        Update,
+        update_objective_label
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// What completes a level.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Split every particle.
+    #[default]
+    ClearParticles,
+    /// Split every killer particle, normal particles may be left.
+    DestroyKillers,
+    /// Stay alive for this much game time.
+    Survive(Duration),
+    /// Nothing, the level never ends.
+    FreePlay,
+}
+
+/// The state of a level the [`Objective`] is checked against.
+pub struct ObjectiveProgress {
+    pub particles: usize,
+    pub killers: usize,
+    /// Game time since the level started.
+    pub elapsed: Duration,
+    pub player_alive: bool,
+}
+
+impl Objective {
+    pub fn is_complete(&self, progress: &ObjectiveProgress) -> bool {
+        match *self {
+            Self::ClearParticles => progress.particles == 0,
+            Self::DestroyKillers => progress.killers == 0,
+            Self::Survive(duration) => progress.player_alive && duration <= progress.elapsed,
+            Self::FreePlay => false,
+        }
+    }
+
+    /// Whether a level without particles has nothing to do, completing right away.
+    pub fn needs_particles(&self) -> bool {
+        matches!(self, Self::ClearParticles | Self::DestroyKillers)
+    }
+
+    /// Shown on the HUD, `elapsed` being the game time since the level started.
+    pub fn description(&self, elapsed: Duration) -> String {
+        match *self {
+            Self::ClearParticles => String::from("Split every particle"),
+            Self::DestroyKillers => String::from("Split every killer"),
+            Self::Survive(duration) => format!(
+                "Survive for {:.1}s",
+                duration.saturating_sub(elapsed).as_secs_f32()
+            ),
+            Self::FreePlay => String::from("Free play"),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ObjectiveLabel;
+
+fn spawn_objective_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Objective"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Px(0.0),
+            width: Percent(100.0),
+            padding: UiRect::all(Px(20.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(widget::label(""), ObjectiveLabel)],
+    ));
+}
+
+fn update_objective_label(
+    level_query: Query<(&RawLevel, &LevelStats)>,
+    mut label: Single<&mut Text, With<ObjectiveLabel>>,
+) {
+    let Ok((level, stats)) = level_query.single() else {
+        return;
+    };
+
+    label.0 = level.0.objective.description(stats.elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objectives_check_their_own_progress() {
+        let progress = ObjectiveProgress {
+            particles: 3,
+            killers: 0,
+            elapsed: Duration::from_secs(5),
+            player_alive: true,
+        };
+
+        assert!(!Objective::ClearParticles.is_complete(&progress));
+        assert!(Objective::DestroyKillers.is_complete(&progress));
+        assert!(Objective::Survive(Duration::from_secs(5)).is_complete(&progress));
+        assert!(!Objective::Survive(Duration::from_secs(6)).is_complete(&progress));
+        assert!(!Objective::FreePlay.is_complete(&progress));
+
+        let dead = ObjectiveProgress {
+            player_alive: false,
+            ..progress
+        };
+        assert!(!Objective::Survive(Duration::from_secs(5)).is_complete(&dead));
+    }
+}
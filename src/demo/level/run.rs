@@ -0,0 +1,86 @@
+//! The run through the default levels, from the first one to the end screen.
+
+use bevy::prelude::*;
+
+use super::{Level, SpawnRawLevel};
+use crate::screens::{Screen, gameplay::SelectedLevel};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunProgress>();
+
+    app.add_observer(track_run_level);
+    app.add_observer(restart_run);
+}
+
+/// Progress of the current run, kept apart from the level entities, which are despawned between
+/// levels. Reset when the run is restarted.
+#[derive(Resource, Default, Debug)]
+pub struct RunProgress {
+    /// The index of the default level the run is on.
+    pub current_default_index: usize,
+}
+
+/// Resets the [`RunProgress`] and plays the first default level.
+#[derive(Event)]
+pub struct RestartRun;
+
+/// Campaign levels can also be entered from the level select.
+///
+/// Plays that record no progress leave the run alone, even of a default level.
+fn track_run_level(trigger: Trigger<SpawnRawLevel>, mut run_progress: ResMut<RunProgress>) {
+    if !trigger.source.records_progress() {
+        return;
+    }
+    if let Some(Level::Default(id)) = trigger.level {
+        run_progress.current_default_index = id;
+    }
+}
+
+fn restart_run(
+    _: Trigger<RestartRun>,
+    mut run_progress: ResMut<RunProgress>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    debug!("Restarting the run");
+    *run_progress = RunProgress::default();
+    // Spawned when entering gameplay.
+    selected_level.0 = Some(Level::Default(run_progress.current_default_index));
+    next_screen.set(Screen::Gameplay);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+    use crate::demo::level::{PlaySource, level_data::LevelData};
+
+    #[test]
+    fn restarting_the_run_starts_from_the_first_level() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.init_state::<Screen>();
+        app.init_resource::<SelectedLevel>();
+        app.add_plugins(plugin);
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData::default(),
+            level: Some(Level::Default(3)),
+            source: PlaySource::Campaign,
+        });
+        assert_eq!(
+            app.world().resource::<RunProgress>().current_default_index,
+            3
+        );
+
+        app.world_mut().trigger(RestartRun);
+        let world = app.world();
+        assert_eq!(world.resource::<RunProgress>().current_default_index, 0);
+        assert_eq!(world.resource::<SelectedLevel>().0, Some(Level::Default(0)));
+        assert!(matches!(
+            world.resource::<NextState<Screen>>(),
+            NextState::Pending(Screen::Gameplay)
+        ));
+    }
+}
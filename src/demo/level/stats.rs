@@ -0,0 +1,198 @@
+//! Score and split combos of the current level, shown on the HUD.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{level::PlaySource, particle::ParticleDespawned, time_scale::Game},
+    screens::Screen,
+    settings::Settings,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LevelStats>();
+    app.register_type::<ComboConfig>();
+    app.init_resource::<ComboConfig>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_hud);
+    app.add_systems(
+        Update,
+        (update_level_stats, update_hud)
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct ComboConfig {
+    /// A split within this much game time of the previous one continues the combo.
+    pub window: Duration,
+    /// Points for a split outside of a combo.
+    pub split_score: u32,
+    /// How much each further split of a combo raises the multiplier.
+    pub multiplier_step: f32,
+    pub max_multiplier: f32,
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs_f32(0.5),
+            split_score: 100,
+            multiplier_step: 0.5,
+            max_multiplier: 5.0,
+        }
+    }
+}
+
+impl ComboConfig {
+    /// The score multiplier of the `count`th split of a combo.
+    pub fn multiplier(&self, count: u32) -> f32 {
+        (1.0 + self.multiplier_step * count.saturating_sub(1) as f32).min(self.max_multiplier)
+    }
+}
+
+/// Stats of the level it is on, reset together with the level on restart.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct LevelStats {
+    pub score: u32,
+    pub splits: u32,
+    /// Splits in the current combo, 0 if there is none.
+    pub combo: u32,
+    /// Game time since the last split.
+    pub since_last_split: Duration,
+    /// Game time since the level started.
+    pub elapsed: Duration,
+}
+
+impl LevelStats {
+    pub fn tick(&mut self, delta: Duration, config: &ComboConfig) {
+        self.since_last_split += delta;
+        self.elapsed += delta;
+        if self.since_last_split > config.window {
+            self.combo = 0;
+        }
+    }
+
+    pub fn record_split(&mut self, config: &ComboConfig) {
+        self.splits += 1;
+        self.combo += 1;
+        self.since_last_split = Duration::ZERO;
+        self.score += (config.split_score as f32 * config.multiplier(self.combo)).round() as u32;
+    }
+}
+
+/// Every particle despawned during gameplay has been split.
+///
+/// Plays that record no progress keep the time, which objectives go by, but don't score.
+pub(super) fn update_level_stats(
+    mut events: EventReader<ParticleDespawned>,
+    mut level_query: Query<(&mut LevelStats, &PlaySource)>,
+    config: Res<ComboConfig>,
+    time: Res<Time<Game>>,
+) {
+    let Ok((mut stats, source)) = level_query.single_mut() else {
+        events.clear();
+        return;
+    };
+
+    stats.tick(time.delta(), &config);
+    if !source.records_progress() {
+        events.clear();
+        return;
+    }
+    for _ in events.read() {
+        stats.record_split(&config);
+    }
+}
+
+#[derive(Component)]
+struct ScoreLabel;
+
+#[derive(Component)]
+struct ComboLabel;
+
+/// How big the combo label pops up when the combo grows.
+const COMBO_POPUP_SCALE: f32 = 1.5;
+
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            padding: UiRect::all(Px(20.0)),
+            justify_content: JustifyContent::SpaceBetween,
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![
+            (widget::label("Score: 0"), ScoreLabel),
+            (widget::label(""), ComboLabel, Visibility::Hidden),
+        ],
+    ));
+}
+
+fn update_hud(
+    level_query: Query<&LevelStats>,
+    config: Res<ComboConfig>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut score_label: Single<&mut Text, (With<ScoreLabel>, Without<ComboLabel>)>,
+    mut combo_label: Single<(&mut Text, &mut Visibility, &mut Transform), With<ComboLabel>>,
+    mut last_combo: Local<u32>,
+) {
+    let Ok(stats) = level_query.single() else {
+        return;
+    };
+
+    score_label.0 = format!("Score: {}", stats.score);
+
+    let (text, visibility, transform) = &mut *combo_label;
+    if stats.combo < 2 {
+        **visibility = Visibility::Hidden;
+    } else {
+        **visibility = Visibility::Inherited;
+        text.0 = format!("Combo x{:.1}", config.multiplier(stats.combo));
+    }
+
+    if stats.combo > *last_combo && !settings.reduce_motion {
+        transform.scale = Vec3::splat(COMBO_POPUP_SCALE);
+    }
+    *last_combo = stats.combo;
+
+    let factor = (10.0 * time.delta_secs()).min(1.0);
+    transform.scale = transform.scale.lerp(Vec3::ONE, factor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combo_multiplies_score_until_the_window_passes() {
+        let config = ComboConfig::default();
+        let mut stats = LevelStats::default();
+
+        stats.record_split(&config);
+        stats.tick(config.window / 2, &config);
+        stats.record_split(&config);
+        assert_eq!(stats.combo, 2);
+        assert_eq!(stats.score, 100 + 150);
+
+        stats.tick(config.window * 2, &config);
+        assert_eq!(stats.combo, 0);
+
+        stats.record_split(&config);
+        assert_eq!(stats.score, 100 + 150 + 100);
+        assert_eq!(stats.splits, 3);
+    }
+}
@@ -0,0 +1,124 @@
+//! An optional countdown per level, the player dies when it runs out.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, ui::Val::*};
+
+use super::LevelState;
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{killer::KillEvent, player::Player, time_scale::Game},
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_time_limit_label);
+    app.add_systems(
+        Update,
+        (tick_time_limit, update_time_limit_label)
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The game time left on a level with a time limit, reset together with the level on restart.
+#[derive(Component)]
+pub struct TimeLimit {
+    pub remaining: Duration,
+}
+
+/// Counts down with [`Time<Game>`], so it slows down with the game and stops while paused.
+fn tick_time_limit(
+    mut level_query: Query<(&mut TimeLimit, &LevelState)>,
+    player_query: Query<Entity, With<Player>>,
+    time: Res<Time<Game>>,
+    mut kill_events: EventWriter<KillEvent>,
+) {
+    for (mut time_limit, level_state) in level_query.iter_mut() {
+        if time_limit.remaining.is_zero() || *level_state == LevelState::Ended {
+            continue;
+        }
+
+        time_limit.remaining = time_limit.remaining.saturating_sub(time.delta());
+        if time_limit.remaining.is_zero() {
+            debug!("Time limit reached");
+            for player in player_query.iter() {
+                kill_events.write(KillEvent { player });
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct TimeLimitLabel;
+
+fn spawn_time_limit_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Time Limit"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            padding: UiRect::all(Px(20.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(widget::label(""), TimeLimitLabel, Visibility::Hidden)],
+    ));
+}
+
+fn update_time_limit_label(
+    level_query: Query<&TimeLimit>,
+    mut label: Single<(&mut Text, &mut Visibility), With<TimeLimitLabel>>,
+) {
+    let (text, visibility) = &mut *label;
+    let Ok(time_limit) = level_query.single() else {
+        **visibility = Visibility::Hidden;
+        return;
+    };
+
+    **visibility = Visibility::Inherited;
+    text.0 = format!("{:.1}", time_limit.remaining.as_secs_f32());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn running_out_of_time_kills_the_player() {
+        let mut world = World::new();
+        world.init_resource::<Time<Game>>();
+        world.init_resource::<Events<KillEvent>>();
+        world.spawn((
+            TimeLimit {
+                remaining: Duration::from_secs(1),
+            },
+            LevelState::Playing,
+        ));
+        let player = world.spawn(Player::default()).id();
+
+        world
+            .resource_mut::<Time<Game>>()
+            .advance_by(Duration::from_millis(600));
+        world.run_system_once(tick_time_limit).unwrap();
+        assert!(world.resource::<Events<KillEvent>>().is_empty());
+
+        world
+            .resource_mut::<Time<Game>>()
+            .advance_by(Duration::from_millis(600));
+        world.run_system_once(tick_time_limit).unwrap();
+        let events = world.resource::<Events<KillEvent>>();
+        let killed: Vec<_> = events
+            .iter_current_update_events()
+            .map(|e| e.player)
+            .collect();
+        assert_eq!(killed, [player]);
+    }
+}
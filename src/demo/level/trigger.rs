@@ -0,0 +1,131 @@
+//! Trigger zones, obstacles that nothing bounces off but that send a named [`LevelTrigger`] when
+//! the player or a particle enters them.
+//!
+//! What a trigger does is up to whoever listens, e.g. opening a gate or spawning particles.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PausableSystems,
+    demo::{particle::Particle, player::Player},
+    physics::{CollisionHandlerSystems, find_rigidbody_ancestor},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<LevelTrigger>();
+
+    app.add_systems(
+        PostUpdate,
+        trigger_collision_handler
+            .in_set(CollisionHandlerSystems)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Turns an obstacle into a trigger zone.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TriggerData {
+    /// Sent with the [`LevelTrigger`], so listeners can tell triggers apart.
+    pub name: String,
+}
+
+/// Sent when the player or a particle enters a trigger zone.
+#[derive(Event, Debug)]
+pub struct LevelTrigger {
+    pub name: String,
+    /// The player or the particle that entered the zone.
+    pub by: Entity,
+}
+
+#[derive(Component)]
+pub struct TriggerZone {
+    pub name: String,
+}
+
+/// Added to an obstacle, turning it into a sensor.
+pub fn trigger_zone(trigger: &TriggerData) -> impl Bundle {
+    (
+        TriggerZone {
+            name: trigger.name.clone(),
+        },
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+    )
+}
+
+fn trigger_collision_handler(
+    mut collision_events: EventReader<CollisionEvent>,
+    ancestor_query: Query<(Option<&RigidBody>, Option<&ChildOf>)>,
+    zone_query: Query<&TriggerZone>,
+    enterer_query: Query<(), Or<(With<Player>, With<Particle>)>>,
+    mut events: EventWriter<LevelTrigger>,
+) {
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = *event else {
+            continue;
+        };
+
+        let Some(e1) = find_rigidbody_ancestor(e1, &ancestor_query) else {
+            continue;
+        };
+        let Some(e2) = find_rigidbody_ancestor(e2, &ancestor_query) else {
+            continue;
+        };
+
+        for (zone, by) in [(e1, e2), (e2, e1)] {
+            let Ok(zone) = zone_query.get(zone) else {
+                continue;
+            };
+            if enterer_query.contains(by) {
+                debug!(name = zone.name, ?by, "Level trigger");
+                events.write(LevelTrigger {
+                    name: zone.name.clone(),
+                    by,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy_rapier2d::rapier::geometry::CollisionEventFlags;
+
+    use super::*;
+
+    #[test]
+    fn entering_a_zone_sends_its_trigger() {
+        let mut world = World::new();
+        world.init_resource::<Events<CollisionEvent>>();
+        world.init_resource::<Events<LevelTrigger>>();
+
+        let zone = world
+            .spawn((
+                RigidBody::Fixed,
+                trigger_zone(&TriggerData {
+                    name: String::from("gate"),
+                }),
+            ))
+            .id();
+        let player = world.spawn((RigidBody::Dynamic, Player::default())).id();
+        let sensor = world.spawn(ChildOf(player)).id();
+        let wall = world.spawn(RigidBody::Fixed).id();
+
+        for (e1, e2) in [(sensor, zone), (zone, wall)] {
+            world.send_event(CollisionEvent::Started(e1, e2, CollisionEventFlags::SENSOR));
+        }
+        world.run_system_once(trigger_collision_handler).unwrap();
+
+        let triggers: Vec<_> = world
+            .resource::<Events<LevelTrigger>>()
+            .iter_current_update_events()
+            .map(|trigger| (trigger.name.as_str(), trigger.by))
+            .collect();
+        assert_eq!(triggers, [("gate", player)]);
+    }
+}
@@ -1,14 +1,22 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
 use arrows::{Arrows, ArrowsAssets, ArrowsConfig, ArrowsOf, arrows};
+use behavior::ParticleBehaviors;
 use bevy::{
-    ecs::{relationship::RelatedSpawner, spawn::SpawnWith, system::QueryLens},
+    ecs::{
+        relationship::RelatedSpawner,
+        spawn::SpawnWith,
+        system::{RunSystemError, RunSystemOnce},
+    },
+    platform::collections::HashSet,
     prelude::*,
 };
 // use bevy_hanabi::{EffectProperties, EffectSpawner};
 use bevy_rapier2d::prelude::*;
+use fuse::Fuse;
 use invincible::{Invincible, InvincibleRemoved};
 use serde::{Deserialize, Serialize};
+use shape::ParticleShape;
 
 use crate::{
     AppSystems, PausableSystems,
@@ -17,69 +25,166 @@ use crate::{
     external::maybe::Maybe,
     physics::{CollisionHandlerSystems, find_rigidbody_ancestor},
     screens::Screen,
+    theme::palette::Theme,
 };
 
 use super::{
-    killer::Killer,
+    editor::EditorSimulation,
+    killer::KillerLook,
+    level::{RawLevel, SpawnRawLevel},
     player::{Player, PlayerConfig},
     time_scale::{SetTimeScale, TimeScaleKind},
 };
 
 pub mod arrows;
+pub mod behavior;
+pub mod fuse;
 pub mod invincible;
+pub mod shape;
+
+/// Spawning, splitting and collision handling of particles.
+///
+/// Can be added by other crates with a custom [`ParticleConfig`].
+pub struct ParticlePlugin {
+    pub config: ParticleConfig,
+    /// Whether the player splits particles on contact.
+    pub handle_player_collisions: bool,
+}
 
-pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((invincible::plugin, arrows::plugin));
-
-    app.init_resource::<ParticleConfig>();
-
-    app.register_type::<ParticleAssets>();
-    app.load_resource::<ParticleAssets>();
-
-    app.add_event::<ParticleSplitEvent>();
-
-    // Collision handling
-
-    app.add_systems(
-        PostUpdate,
-        (
-            particle_collision_handler
-                .in_set(CollisionHandlerSystems)
-                .in_set(PausableSystems)
-                .run_if(in_state(Screen::Gameplay)),
-            split_particle
-                .after(CollisionHandlerSystems)
-                .run_if(in_state(Screen::Gameplay)),
-        ),
-    );
-
-    app.add_observer(player_particle_collision);
-    app.add_observer(particle_particle_collision);
-    app.add_observer(spawn_particle);
-
-    app.add_event::<ParticleSpawned>();
-    app.add_event::<ParticleDespawned>();
+impl Default for ParticlePlugin {
+    fn default() -> Self {
+        Self {
+            config: ParticleConfig::default(),
+            handle_player_collisions: true,
+        }
+    }
+}
 
-    // Invincibility
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            invincible::plugin,
+            fuse::plugin,
+            arrows::plugin,
+            behavior::plugin,
+        ));
+
+        app.insert_resource(self.config.clone());
+        app.init_resource::<ParticlePool>();
+        app.add_observer(clear_particle_pool);
+        app.init_resource::<SplitQueue>();
+        // Queued particles belong to the level they were hit in.
+        app.add_observer(clear_split_queue_on_spawn);
+        app.add_systems(OnExit(Screen::Gameplay), clear_split_queue);
+        app.init_resource::<PopSounds>();
+        app.register_type::<ParticleSpeedScale>();
+        app.init_resource::<ParticleSpeedScale>();
+
+        app.register_type::<ParticleAssets>();
+        app.load_resource::<ParticleAssets>();
+
+        app.add_event::<ParticleSplitEvent>();
+
+        // Collision handling
+
+        app.add_systems(
+            PostUpdate,
+            (
+                reset_pop_sounds.before(CollisionHandlerSystems),
+                particle_collision_handler
+                    .in_set(CollisionHandlerSystems)
+                    .in_set(PausableSystems)
+                    .run_if(particles_simulated),
+                split_particle
+                    .after(CollisionHandlerSystems)
+                    .run_if(particles_simulated),
+            ),
+        );
+
+        if self.handle_player_collisions {
+            app.add_observer(player_particle_collision);
+        }
+        app.add_observer(particle_particle_collision);
+        app.add_observer(spawn_particle);
+
+        app.add_event::<ParticleSpawned>();
+        app.add_event::<ParticleDespawned>();
+
+        // Invincibility
+
+        app.add_systems(
+            Update,
+            (
+                invincibility_added
+                    .in_set(AppSystems::Update)
+                    .run_if(particles_simulated),
+                invincibility_removed
+                    .in_set(AppSystems::Update)
+                    .run_if(particles_simulated),
+                update_invincible_material.run_if(
+                    resource_exists::<ParticleAssets>
+                        .and(resource_changed::<Theme>.or(resource_added::<ParticleAssets>)),
+                ),
+                update_particle_ccd
+                    .in_set(AppSystems::Update)
+                    .in_set(PausableSystems)
+                    .run_if(particles_simulated),
+            ),
+        );
+    }
+}
 
-    app.add_systems(
-        Update,
-        (
-            invincibility_added
-                .in_set(AppSystems::Update)
-                .run_if(in_state(Screen::Gameplay)),
-            invincibility_removed
-                .in_set(AppSystems::Update)
-                .run_if(in_state(Screen::Gameplay)),
-        ),
-    );
+/// Particles collide, split and lose their invincibility while playing and while the editor
+/// simulates a level.
+fn particles_simulated(
+    screen: Option<Res<State<Screen>>>,
+    simulation: Option<Res<EditorSimulation>>,
+) -> bool {
+    simulation.is_some() || screen.is_some_and(|screen| *screen.get() == Screen::Gameplay)
 }
 
-#[derive(Resource, Reflect)]
+#[derive(Resource, Reflect, Clone)]
 #[reflect(Resource)]
 pub struct ParticleConfig {
     pub local_z: f32,
     pub invincibility_duration: Duration,
+    /// The maximum number of split particle entities kept around for reuse.
+    pub pool_capacity: usize,
+    /// The maximum number of particles split per frame, the rest are split in the following frames.
+    pub max_splits_per_frame: usize,
+    pub collision_groups: ParticleCollisionGroups,
+    /// Whether invincible particles bounce off the player, otherwise they pass through it like
+    /// any other particle.
+    pub invincible_collides_with_player: bool,
+    /// The speed a [`ParticleSpeedScale`] above one can't push particles past.
+    pub max_scaled_speed: f32,
+    /// [`ParticleCcd::Always`] by default, as particles always used CCD before it could be set.
+    pub ccd: ParticleCcd,
+    /// The most pop sounds played per frame, so chain reactions don't stack them into noise.
+    /// Zero mutes them.
+    pub max_pops_per_frame: usize,
+}
+
+/// When particles use continuous collision detection, which keeps fast particles from tunneling
+/// through thin obstacles and walls.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub enum ParticleCcd {
+    Always,
+    Never,
+    /// Only while faster than `min_speed`, as CCD is costly with many particles.
+    AboveSpeed {
+        min_speed: f32,
+    },
+}
+
+impl ParticleCcd {
+    pub fn enabled(&self, speed: f32) -> bool {
+        match *self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::AboveSpeed { min_speed } => min_speed < speed,
+        }
+    }
 }
 
 impl Default for ParticleConfig {
@@ -87,15 +192,186 @@ impl Default for ParticleConfig {
         Self {
             local_z: -2.0,
             invincibility_duration: Duration::from_secs_f32(0.5),
+            pool_capacity: 256,
+            max_splits_per_frame: 32,
+            collision_groups: ParticleCollisionGroups::default(),
+            invincible_collides_with_player: true,
+            max_scaled_speed: 1000.0,
+            ccd: ParticleCcd::Always,
+            max_pops_per_frame: 4,
         }
     }
 }
 
+impl ParticleConfig {
+    /// The collision groups of a particle body while it is or isn't invincible.
+    pub fn body_collision_groups(&self, invincible: bool) -> CollisionGroups {
+        if invincible && self.invincible_collides_with_player {
+            self.collision_groups.invincible
+        } else {
+            self.collision_groups.normal
+        }
+    }
+}
+
+/// Scales the initial velocity of every spawned particle, set from the
+/// [`super::difficulty::Difficulty`] setting.
+///
+/// Levels keep their authored velocities, the scale is only applied to the spawned bodies. Arrows
+/// still show the authored velocities.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct ParticleSpeedScale(pub f32);
+
+impl Default for ParticleSpeedScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl ParticleSpeedScale {
+    /// Scales `velocity`, speeding up at most to `max_speed` so fast particles don't tunnel
+    /// through walls. Velocities authored faster than that are left as they are.
+    pub fn apply(&self, velocity: Vec2, max_speed: f32) -> Vec2 {
+        (self.0 * velocity).clamp_length_max(max_speed.max(velocity.length()))
+    }
+
+    /// The authored velocity `velocity` was scaled from, to save particles in play into a level.
+    /// Scaling it again gives `velocity` back, unless it is faster than the `max_speed` of
+    /// [`Self::apply`].
+    pub fn unscale(&self, velocity: Vec2) -> Vec2 {
+        velocity / self.0
+    }
+}
+
+/// The collision groups of particles, see [`super::player`] for the default setup.
+///
+/// By default particle bodies only collide with the walls and obstacles, particles and the player
+/// meet through their sensors instead. Invincible particles move into the group of the player's
+/// body, so they bounce off the player and off each other rather than passing through.
+///
+/// The sensors keep their group throughout, so a particle losing its invincibility while touching
+/// the player isn't split right away, the contact started while it was still invincible. It is
+/// split the next time the player hits it.
+#[derive(Reflect, Clone, Copy)]
+pub struct ParticleCollisionGroups {
+    pub normal: CollisionGroups,
+    pub invincible: CollisionGroups,
+    pub sensor: CollisionGroups,
+}
+
+impl Default for ParticleCollisionGroups {
+    fn default() -> Self {
+        Self {
+            normal: CollisionGroups::new(Group::GROUP_3, Group::GROUP_1),
+            invincible: CollisionGroups::new(Group::GROUP_2, Group::GROUP_1 | Group::GROUP_2),
+            sensor: CollisionGroups::new(Group::GROUP_3, Group::GROUP_3),
+        }
+    }
+}
+
+/// Particles waiting to be split by [`split_particle`], in the order they were hit.
+#[derive(Resource, Default)]
+pub struct SplitQueue {
+    order: VecDeque<Entity>,
+    /// The same particles, to find one hit again while queued without going through the queue.
+    queued: HashSet<Entity>,
+}
+
+impl SplitQueue {
+    /// Queues the particle unless it already is.
+    fn push(&mut self, entity: Entity) {
+        if self.queued.insert(entity) {
+            self.order.push_back(entity);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Entity> {
+        let entity = self.order.pop_front()?;
+        self.queued.remove(&entity);
+        Some(entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.order.clear();
+        self.queued.clear();
+    }
+}
+
+fn clear_split_queue_on_spawn(_: Trigger<SpawnRawLevel>, mut queue: ResMut<SplitQueue>) {
+    queue.clear();
+}
+
+fn clear_split_queue(mut queue: ResMut<SplitQueue>) {
+    queue.clear();
+}
+
+/// Particle entities that were split and can be reused by [`spawn_particle`]
+/// instead of spawning new ones.
+#[derive(Resource, Default)]
+pub struct ParticlePool(Vec<Entity>);
+
+/// Pooled particles are parked in the level they were split in, so they go with it.
+fn clear_particle_pool(
+    _: Trigger<OnRemove, RawLevel>,
+    mut pool: ResMut<ParticlePool>,
+    mut commands: Commands,
+) {
+    for entity in pool.0.drain(..) {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// Marker for a particle entity that is parked in the [`ParticlePool`].
+#[derive(Component)]
+pub struct PooledParticle;
+
+/// Strips the particle related components from the entity, so it can be parked in the pool.
+fn park_particle(
+    entity: Entity,
+    kind: &ParticleKind,
+    behaviors: &ParticleBehaviors,
+    commands: &mut Commands,
+) {
+    let mut entity_commands = commands.entity(entity);
+    if let Some(behavior) = behaviors.get(kind) {
+        behavior.remove(&mut entity_commands);
+    }
+
+    entity_commands
+        .despawn_related::<Children>()
+        .despawn_related::<Arrows>()
+        .remove::<(
+            Particle,
+            Mesh2d,
+            MeshMaterial2d<ColorMaterial>,
+            RigidBody,
+            Ccd,
+            Restitution,
+            Collider,
+            Velocity,
+            CollisionGroups,
+            KillerLook,
+            Invincible,
+            Fuse,
+        )>()
+        .insert((Visibility::Hidden, PooledParticle));
+}
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct ParticleAssets {
     #[dependency]
     pop_sound: Handle<AudioSource>,
+    /// Colored from the current [`Theme`].
     invincible_material: Handle<ColorMaterial>,
 }
 
@@ -105,9 +381,7 @@ impl FromWorld for ParticleAssets {
         let pop_sound = assets.load("audio/sound_effects/pop.ogg");
 
         let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
-        let invincible_material = materials.add(ColorMaterial::from_color(Color::Srgba(
-            Srgba::hex("f7bd1d").unwrap(),
-        )));
+        let invincible_material = materials.add(ColorMaterial::default());
 
         Self {
             pop_sound,
@@ -116,19 +390,32 @@ impl FromWorld for ParticleAssets {
     }
 }
 
-#[derive(Debug, Clone, Reflect, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Selects the behavior of a particle, see [`behavior`].
+#[derive(Debug, Clone, Reflect, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum ParticleKind {
     #[default]
     Normal,
     Killer,
+    /// A kind registered with [`behavior::RegisterParticleKind`].
+    Custom(String),
 }
 
 #[derive(Component, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Particle {
     pub kind: ParticleKind,
     pub radius: f32,
+    /// Balls unless set, so older levels are unaffected.
+    pub shape: ParticleShape,
     pub color: Color,
     pub initial_velocity: Vec2,
+    /// In radians per second, counterclockwise.
+    pub initial_angvel: f32,
+    /// Splits the particle after this much game time, without it being hit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fuse: Option<Duration>,
+    /// Older levels were saved from `Vec<Box<Particle>>`, serde serializes boxes transparently,
+    /// so those files deserialize into this as well.
     pub subparticles: Vec<Particle>,
 }
 
@@ -137,23 +424,50 @@ impl Default for Particle {
         Self {
             kind: ParticleKind::default(),
             radius: 20.0,
+            shape: ParticleShape::default(),
             color: Color::Srgba(Srgba::hex("0f95e2").unwrap()),
             initial_velocity: Vec2::ZERO,
+            initial_angvel: 0.0,
+            fuse: None,
             subparticles: Vec::new(),
         }
     }
 }
 
+impl Particle {
+    /// Applies `f` to the initial velocity of the particle and its subparticles.
+    pub fn map_velocities(&mut self, f: &impl Fn(Vec2) -> Vec2) {
+        self.initial_velocity = f(self.initial_velocity);
+
+        for subparticle in &mut self.subparticles {
+            subparticle.map_velocities(f);
+        }
+    }
+
+    /// Reverses the spin of the particle and its subparticles, for mirroring them.
+    pub fn reverse_angvels(&mut self) {
+        self.initial_angvel = -self.initial_angvel;
+
+        for subparticle in &mut self.subparticles {
+            subparticle.reverse_angvels();
+        }
+    }
+}
+
 pub fn particle(
     translation: Vec2,
     particle: Particle,
     particle_config: &ParticleConfig,
+    speed_scale: ParticleSpeedScale,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
 ) -> impl Bundle {
     // TODO crate a cache for these
-    let mesh = meshes.add(Circle::new(particle.radius));
+    let mesh = meshes.add(particle.shape.mesh(particle.radius));
     let material = materials.add(particle.color);
+    let collider = particle.shape.collider(particle.radius);
+    let linvel = speed_scale.apply(particle.initial_velocity, particle_config.max_scaled_speed);
+    let fuse = particle.fuse.map(Fuse::new);
 
     (
         Name::new("Particle"),
@@ -161,22 +475,25 @@ pub fn particle(
         Mesh2d(mesh),
         MeshMaterial2d(material),
         RigidBody::Dynamic,
-        Ccd::enabled(),
-        Restitution::coefficient(0.5),
-        Collider::ball(particle.radius),
+        Ccd {
+            enabled: particle_config.ccd.enabled(linvel.length()),
+        },
+        // How much particles bounce is up to what they hit, see `level_bounds` and `obstacle`.
+        Restitution::coefficient(0.0),
+        collider.clone(),
         children![(
             Name::new("Particle Sensor"),
             ActiveEvents::COLLISION_EVENTS,
-            CollisionGroups::new(Group::GROUP_3, Group::GROUP_3),
-            Collider::ball(particle.radius),
+            particle_config.collision_groups.sensor,
+            collider,
             Sensor
         )],
         Velocity {
-            linvel: particle.initial_velocity,
-            angvel: 0.0,
+            linvel,
+            angvel: particle.initial_angvel,
         },
-        CollisionGroups::new(Group::GROUP_3, Group::GROUP_1),
-        Maybe((particle.kind == ParticleKind::Killer).then_some(Killer)),
+        particle_config.collision_groups.normal,
+        Maybe(fuse),
         particle,
     )
 }
@@ -185,7 +502,9 @@ pub fn particle_bundle(
     translation: Vec2,
     particle: Particle,
     spawn_as_invincible: bool,
+    show_arrows: bool,
     particle_config: &ParticleConfig,
+    speed_scale: ParticleSpeedScale,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
     arrows_config: &ArrowsConfig,
@@ -207,62 +526,91 @@ pub fn particle_bundle(
     };
 
     (
-        Arrows::spawn(SpawnWith(spawn_list)),
+        Maybe(show_arrows.then(|| Arrows::spawn(SpawnWith(spawn_list)))),
         Maybe(
             spawn_as_invincible.then_some(Invincible::new(particle_config.invincibility_duration)),
         ),
-        self::particle(translation, particle, particle_config, meshes, materials),
+        self::particle(
+            translation,
+            particle,
+            particle_config,
+            speed_scale,
+            meshes,
+            materials,
+        ),
     )
 }
 
 // System that triggers specialized collision events.
 pub fn particle_collision_handler(
     mut collision_events: EventReader<CollisionEvent>,
-    mut query: Query<(
-        Option<&Particle>,
-        Option<&Player>,
-        Option<&RigidBody>,
-        &ChildOf,
-    )>,
+    ancestor_query: Query<(Option<&RigidBody>, Option<&ChildOf>)>,
+    query: Query<(Has<Particle>, Has<Player>), Or<(With<Particle>, With<Player>)>>,
     mut commands: Commands,
 ) {
+    let _span = debug_span!("particle_collision_handler").entered();
     for event in collision_events.read() {
         let CollisionEvent::Started(e1, e2, _) = *event else {
-            return;
+            continue;
         };
 
-        let mut helper_lens: QueryLens<(Option<&RigidBody>, &ChildOf)> = query.transmute_lens();
-        let helper_query = helper_lens.query();
-        let Some(e1) = find_rigidbody_ancestor(e1, &helper_query) else {
-            return;
+        let Some(e1) = find_rigidbody_ancestor(e1, &ancestor_query) else {
+            continue;
         };
-        let Some(e2) = find_rigidbody_ancestor(e2, &helper_query) else {
-            return;
+        let Some(e2) = find_rigidbody_ancestor(e2, &ancestor_query) else {
+            continue;
         };
 
-        let (e1_particle, e1_player, _, _) = query.get(e1).unwrap();
-        let (e2_particle, e2_player, _, _) = query.get(e2).unwrap();
+        // Skip pairs that don't involve both a particle and a player or another particle.
+        let (Ok((e1_particle, e1_player)), Ok((e2_particle, e2_player))) =
+            (query.get(e1), query.get(e2))
+        else {
+            continue;
+        };
 
-        if e1_player.is_some() && e2_particle.is_some() {
+        if e1_player && e2_particle {
             commands.trigger(PlayerParticleCollisionEvent { particle: e2 });
-            return;
+            continue;
         }
 
-        if e2_player.is_some() && e1_particle.is_some() {
+        if e2_player && e1_particle {
             commands.trigger(PlayerParticleCollisionEvent { particle: e1 });
-            return;
+            continue;
         }
 
-        if e1_particle.is_some() && e2_particle.is_some() {
+        if e1_particle && e2_particle {
             commands.trigger(ParticleParticleCollisionEvent {
                 particle1: e1,
                 particle2: e2,
             });
-            return;
         }
     }
 }
 
+/// Counts the pop sounds played this frame, see [`ParticleConfig::max_pops_per_frame`].
+#[derive(Resource, Default)]
+pub struct PopSounds {
+    /// Set from the level being played.
+    pub muted: bool,
+    played: usize,
+}
+
+impl PopSounds {
+    /// Whether another pop sound may play this frame, counting it if so.
+    fn try_play(&mut self, max_per_frame: usize) -> bool {
+        if self.muted || max_per_frame <= self.played {
+            return false;
+        }
+
+        self.played += 1;
+        true
+    }
+}
+
+fn reset_pop_sounds(mut pop_sounds: ResMut<PopSounds>) {
+    pop_sounds.played = 0;
+}
+
 #[derive(Event)]
 pub struct PlayerParticleCollisionEvent {
     pub particle: Entity,
@@ -273,7 +621,10 @@ fn player_particle_collision(
     trigger: Trigger<PlayerParticleCollisionEvent>,
     mut player_query: Query<(&mut Player, &mut Velocity)>,
     mut particle_query: Query<Option<&Invincible>, (With<Particle>, Without<Player>)>,
+    player_config: Res<PlayerConfig>,
+    particle_config: Res<ParticleConfig>,
     particle_assets: Res<ParticleAssets>,
+    mut pop_sounds: ResMut<PopSounds>,
     mut split_events: EventWriter<ParticleSplitEvent>,
     mut time_events: EventWriter<SetTimeScale>,
     mut commands: Commands,
@@ -284,14 +635,16 @@ fn player_particle_collision(
     }
 
     let (mut player, mut velocity) = player_query.single_mut().unwrap();
-    player.can_move = true;
+    player.hit_particle(player_config.relaunch);
 
     // velocity.linvel = Vec2::ZERO;
 
     time_events.write(SetTimeScale(TimeScaleKind::Slowed));
 
     split_events.write(ParticleSplitEvent(trigger.particle));
-    commands.spawn(sound_effect(particle_assets.pop_sound.clone()));
+    if pop_sounds.try_play(particle_config.max_pops_per_frame) {
+        commands.spawn(sound_effect(particle_assets.pop_sound.clone()));
+    }
 }
 
 #[derive(Event)]
@@ -302,14 +655,18 @@ pub struct ParticleParticleCollisionEvent {
 
 fn particle_particle_collision(
     trigger: Trigger<ParticleParticleCollisionEvent>,
+    particle_config: Res<ParticleConfig>,
     particle_assets: Res<ParticleAssets>,
+    mut pop_sounds: ResMut<PopSounds>,
     mut split_events: EventWriter<ParticleSplitEvent>,
     mut commands: Commands,
 ) {
     split_events.write(ParticleSplitEvent(trigger.particle1));
     split_events.write(ParticleSplitEvent(trigger.particle2));
 
-    commands.spawn(sound_effect(particle_assets.pop_sound.clone()));
+    if pop_sounds.try_play(particle_config.max_pops_per_frame) {
+        commands.spawn(sound_effect(particle_assets.pop_sound.clone()));
+    }
 }
 
 #[derive(Event)]
@@ -318,7 +675,7 @@ pub struct ParticleSplitEvent(pub Entity);
 #[derive(Event)]
 pub struct ParticleDespawned;
 
-fn split_particle(
+pub fn split_particle(
     mut events: EventReader<ParticleSplitEvent>,
     mut particle_query: Query<
         (
@@ -327,10 +684,15 @@ fn split_particle(
             &Transform,
             &mut Particle,
             Option<&ChildOf>,
+            Has<Arrows>,
         ),
         Without<Player>,
     >,
     player_config: Res<PlayerConfig>,
+    particle_config: Res<ParticleConfig>,
+    behaviors: Res<ParticleBehaviors>,
+    mut queue: ResMut<SplitQueue>,
+    mut pool: ResMut<ParticlePool>,
     mut commands: Commands,
     mut despawned_events: EventWriter<ParticleDespawned>,
     // mut effect: Query<
@@ -338,12 +700,23 @@ fn split_particle(
     //     Without<Particle>,
     // >,
 ) {
+    let _span = debug_span!("split_particle").entered();
+    // A particle may be hit multiple times in a frame, but it should only be split once.
     for event in events.read() {
-        let (entity, invincible, transform, mut particle, parent) =
-            particle_query.get_mut(event.0).unwrap();
+        queue.push(event.0);
+    }
+
+    let count = queue.len().min(particle_config.max_splits_per_frame);
+    for entity in std::iter::from_fn(|| queue.pop()).take(count) {
+        // The particle might have been despawned since it was queued, e.g. by a restart.
+        let Ok((entity, invincible, transform, mut particle, parent, show_arrows)) =
+            particle_query.get_mut(entity)
+        else {
+            continue;
+        };
 
         if invincible.is_some() {
-            return;
+            continue;
         }
 
         let position = transform.translation;
@@ -368,8 +741,25 @@ fn split_particle(
         // effect_spawner.reset();
 
         let sub_particles = std::mem::take(&mut particle.subparticles);
+        let radius = particle.radius;
+        debug!(
+            particle = ?entity,
+            kind = ?particle.kind,
+            subparticles = sub_particles.len(),
+            "Particle split"
+        );
+
+        // Park the particle before spawning the subparticles, so they can already reuse it.
+        if pool.0.len() < particle_config.pool_capacity {
+            park_particle(entity, &particle.kind, &behaviors, &mut commands);
+            pool.0.push(entity);
+        } else {
+            commands.entity(entity).despawn();
+        }
+        despawned_events.write(ParticleDespawned);
+
         for subparticle in sub_particles {
-            let offset_distance = particle.radius + 2.0 * player_config.radius + subparticle.radius;
+            let offset_distance = radius + 2.0 * player_config.radius + subparticle.radius;
             let offset = subparticle.initial_velocity.normalize() * offset_distance;
 
             let spawn_position = position.xy() + offset;
@@ -378,12 +768,10 @@ fn split_particle(
                 translation: spawn_position,
                 particle: subparticle,
                 spawn_with_invincible: true,
+                show_arrows,
                 parent: parent.map(|x| x.0),
             });
         }
-
-        commands.entity(entity).despawn();
-        despawned_events.write(ParticleDespawned);
     }
 }
 
@@ -392,6 +780,8 @@ pub struct SpawnParticle {
     pub translation: Vec2,
     pub particle: Particle,
     pub spawn_with_invincible: bool,
+    /// Whether to spawn the arrows showing where the subparticles go, subparticles inherit it.
+    pub show_arrows: bool,
     pub parent: Option<Entity>,
 }
 
@@ -401,19 +791,42 @@ pub struct ParticleSpawned;
 fn spawn_particle(
     mut trigger: Trigger<SpawnParticle>,
     particle_config: Res<ParticleConfig>,
+    speed_scale: Res<ParticleSpeedScale>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     arrows_config: Res<ArrowsConfig>,
     arrows_assets: Res<ArrowsAssets>,
+    behaviors: Res<ParticleBehaviors>,
+    mut pool: ResMut<ParticlePool>,
+    pooled_query: Query<(), With<PooledParticle>>,
     mut spawned_events: EventWriter<ParticleSpawned>,
     mut commands: Commands,
 ) {
-    commands.spawn((
+    let kind = trigger.particle.kind.clone();
+
+    // Reuse a pooled entity if there is one, otherwise fall back to spawning a new one.
+    // Pooled entities may have been despawned together with their level in the meantime.
+    let mut entity_commands = loop {
+        match pool.0.pop() {
+            Some(entity) if pooled_query.contains(entity) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.remove::<PooledParticle>();
+                break entity_commands;
+            }
+            Some(_) => continue,
+            None => break commands.spawn_empty(),
+        }
+    };
+
+    entity_commands.insert((
+        Visibility::default(),
         particle_bundle(
             trigger.translation,
             std::mem::take(&mut trigger.particle),
             trigger.spawn_with_invincible,
+            trigger.show_arrows,
             &particle_config,
+            *speed_scale,
             meshes.as_mut(),
             materials.as_mut(),
             &arrows_config,
@@ -423,47 +836,700 @@ fn spawn_particle(
         Maybe(trigger.parent.map(ChildOf)),
     ));
 
+    // Invincible particles get their behavior once the invincibility is gone.
+    let behavior = behaviors
+        .get(&kind)
+        .filter(|_| !trigger.spawn_with_invincible);
+    if let Some(behavior) = behavior {
+        behavior.insert(&mut entity_commands);
+    }
+
     spawned_events.write(ParticleSpawned);
 }
 
+/// Switches CCD of particles on and off with their speed, see [`ParticleCcd::AboveSpeed`].
+fn update_particle_ccd(
+    particle_config: Res<ParticleConfig>,
+    mut query: Query<(&Velocity, &mut Ccd), With<Particle>>,
+) {
+    if !matches!(particle_config.ccd, ParticleCcd::AboveSpeed { .. }) {
+        return;
+    }
+
+    for (velocity, mut ccd) in query.iter_mut() {
+        let enabled = particle_config.ccd.enabled(velocity.linvel.length());
+        if ccd.enabled != enabled {
+            ccd.enabled = enabled;
+        }
+    }
+}
+
 fn invincibility_added(
-    mut query: Query<
-        (Entity, &mut MeshMaterial2d<ColorMaterial>),
-        (With<Particle>, Added<Invincible>),
-    >,
+    mut query: Query<(Entity, &mut MeshMaterial2d<ColorMaterial>, &Particle), Added<Invincible>>,
+    particle_config: Res<ParticleConfig>,
     particle_assets: Res<ParticleAssets>,
+    behaviors: Res<ParticleBehaviors>,
     mut commands: Commands,
 ) {
-    for (entity, mut material) in query.iter_mut() {
+    for (entity, mut material, particle) in query.iter_mut() {
         // Move particle back to collision group 2 so that it collides with the player.
-        commands
-            .entity(entity)
-            .remove::<(CollisionGroups, Killer)>()
-            .insert(CollisionGroups::new(
-                Group::GROUP_2,
-                Group::GROUP_1 | Group::GROUP_2,
-            ));
+        let mut entity_commands = commands.entity(entity);
+        entity_commands
+            .remove::<CollisionGroups>()
+            .insert(particle_config.body_collision_groups(true));
+        if let Some(behavior) = behaviors.get(&particle.kind) {
+            behavior.remove(&mut entity_commands);
+        }
         material.0 = particle_assets.invincible_material.clone();
     }
 }
 
+fn update_invincible_material(
+    particle_assets: Res<ParticleAssets>,
+    theme: Res<Theme>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if let Some(material) = materials.get_mut(&particle_assets.invincible_material) {
+        material.color = theme.hazard.invincible;
+    }
+}
+
 fn invincibility_removed(
     mut events: EventReader<InvincibleRemoved>,
     mut query: Query<(Entity, &mut MeshMaterial2d<ColorMaterial>, &Particle)>,
+    particle_config: Res<ParticleConfig>,
+    behaviors: Res<ParticleBehaviors>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut commands: Commands,
 ) {
     for event in events.read() {
-        let entity = event.0;
-        let (entity, mut material, particle) = query.get_mut(entity).unwrap();
+        // The particle might have been split in the meantime.
+        let Ok((entity, mut material, particle)) = query.get_mut(event.0) else {
+            continue;
+        };
 
         // TODO crate a cache for these
         material.0 = materials.add(particle.color);
 
-        commands
-            .entity(entity)
+        let mut entity_commands = commands.entity(entity);
+        entity_commands
             .remove::<CollisionGroups>()
-            .insert(CollisionGroups::new(Group::GROUP_3, Group::GROUP_1))
-            .insert_if(Killer, || particle.kind == ParticleKind::Killer);
+            .insert(particle_config.body_collision_groups(false));
+        if let Some(behavior) = behaviors.get(&particle.kind) {
+            behavior.insert(&mut entity_commands);
+        }
+    }
+}
+
+/// An app with just the [`ParticlePlugin`], without a window, rendering or physics.
+///
+/// Particles are meant to be driven directly, e.g. with [`split_all_particles`], the plugin's
+/// systems never run as there is no [`Screen`].
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        crate::asset_tracking::plugin,
+    ));
+
+    app.init_asset::<Mesh>();
+    app.init_asset::<ColorMaterial>();
+    app.init_asset::<Image>();
+    app.init_asset::<AudioSource>();
+
+    app.add_plugins(ParticlePlugin::default());
+    app.init_resource::<ArrowsAssets>();
+    app.init_resource::<PlayerConfig>();
+
+    app
+}
+
+/// A [`headless_app`] that steps the physics without gravity, by a 60th of a second each update.
+#[cfg(test)]
+pub(crate) fn physics_app() -> App {
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_rapier2d::rapier::prelude::IntegrationParameters;
+
+    let mut app = headless_app();
+    app.add_plugins((
+        TransformPlugin,
+        RapierPhysicsPlugin::<NoUserData>::default().with_custom_initialization(
+            RapierContextInitialization::InitializeDefaultRapierContext {
+                integration_parameters: IntegrationParameters::default(),
+                rapier_configuration: RapierConfiguration {
+                    gravity: Vec2::ZERO,
+                    ..RapierConfiguration::new(1.0)
+                },
+            },
+        ),
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / 60.0,
+    )));
+    // Ticks invincibility and fuses, which particles without them don't need.
+    app.init_resource::<Time<super::time_scale::Game>>();
+
+    app
+}
+
+/// Splits every particle and every particle spawned by the splits until none are left, as if
+/// each one got hit in a chain reaction.
+pub fn split_all_particles(world: &mut World) -> Result<(), RunSystemError> {
+    loop {
+        let particles: Vec<Entity> = world
+            .query_filtered::<Entity, With<Particle>>()
+            .iter(world)
+            .collect();
+        if particles.is_empty() {
+            return Ok(());
+        }
+
+        for entity in particles {
+            // Subparticles spawn invincible, there is no time passing for it to wear off.
+            world.entity_mut(entity).remove::<Invincible>();
+            world.send_event(ParticleSplitEvent(entity));
+        }
+        world.run_system_once(split_particle)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_rapier2d::rapier::geometry::CollisionEventFlags;
+
+    use super::{
+        behavior::{ComponentBehavior, RegisterParticleKind},
+        *,
+    };
+    use crate::{
+        audio::SoundEffect,
+        demo::{
+            killer::{KillEvent, Killer, killer_collision_handler},
+            level::{PlaySource, level_data::LevelData},
+            player::Player,
+        },
+    };
+
+    fn test_app() -> App {
+        headless_app()
+    }
+
+    #[test]
+    fn spawning_a_level_clears_the_split_queue() {
+        let mut app = test_app();
+        // Keeps every split queued.
+        app.insert_resource(ParticleConfig {
+            max_splits_per_frame: 0,
+            ..default()
+        });
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle::default(),
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+        let particle = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        for _ in 0..2 {
+            app.world_mut().send_event(ParticleSplitEvent(particle));
+        }
+        app.world_mut().run_system_once(split_particle).unwrap();
+        assert_eq!(app.world().resource::<SplitQueue>().len(), 1);
+
+        app.world_mut().trigger(SpawnRawLevel {
+            data: LevelData::default(),
+            level: None,
+            source: PlaySource::Sandbox,
+        });
+        assert!(app.world().resource::<SplitQueue>().is_empty());
+    }
+
+    #[test]
+    fn split_particles_are_reused() {
+        let mut app = test_app();
+        let level = app.world_mut().spawn(RawLevel(LevelData::default())).id();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                subparticles: vec![Particle {
+                    initial_velocity: Vec2::X,
+                    ..default()
+                }],
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: true,
+            parent: Some(level),
+        });
+        app.world_mut().flush();
+
+        let original = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut().send_event(ParticleSplitEvent(original));
+        app.world_mut().run_system_once(split_particle).unwrap();
+
+        let subparticle = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        assert_eq!(subparticle, original);
+        assert!(app.world().get::<PooledParticle>(subparticle).is_none());
+        assert!(app.world().resource::<ParticlePool>().0.is_empty());
+
+        app.world_mut()
+            .entity_mut(subparticle)
+            .remove::<Invincible>();
+        app.world_mut().send_event(ParticleSplitEvent(subparticle));
+        app.world_mut().run_system_once(split_particle).unwrap();
+        assert_eq!(app.world().resource::<ParticlePool>().0, [subparticle]);
+
+        // Restarting despawns the level, and the parked particle with it.
+        app.world_mut().despawn(level);
+        assert!(app.world().resource::<ParticlePool>().0.is_empty());
+        assert!(app.world().get_entity(subparticle).is_err());
+    }
+
+    #[test]
+    fn hidden_arrows_stay_hidden_for_subparticles() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                subparticles: vec![Particle {
+                    initial_velocity: Vec2::X,
+                    ..default()
+                }],
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let mut arrows_query = app.world_mut().query_filtered::<(), With<ArrowsOf>>();
+        assert_eq!(arrows_query.iter(app.world()).count(), 0);
+
+        let particle = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut().send_event(ParticleSplitEvent(particle));
+        app.world_mut().run_system_once(split_particle).unwrap();
+
+        assert_eq!(arrows_query.iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn custom_kinds_get_their_behavior() {
+        #[derive(Component, Clone)]
+        struct Sticky;
+
+        let mut app = test_app();
+        app.register_particle_kind("sticky", ComponentBehavior(Sticky));
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                kind: ParticleKind::Custom("sticky".into()),
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: true,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let mut query = app.world_mut().query_filtered::<(), With<Sticky>>();
+        assert!(query.single(app.world()).is_ok());
+    }
+
+    #[test]
+    fn boxed_subparticles_deserialize() {
+        // The layout levels were saved from before subparticles were unboxed.
+        #[allow(clippy::vec_box)]
+        #[derive(Serialize)]
+        struct BoxedParticle {
+            kind: ParticleKind,
+            radius: f32,
+            color: Color,
+            initial_velocity: Vec2,
+            subparticles: Vec<Box<BoxedParticle>>,
+        }
+
+        let boxed = BoxedParticle {
+            kind: ParticleKind::Normal,
+            radius: 20.0,
+            color: Color::WHITE,
+            initial_velocity: Vec2::ZERO,
+            subparticles: vec![Box::new(BoxedParticle {
+                kind: ParticleKind::Killer,
+                radius: 10.0,
+                color: Color::BLACK,
+                initial_velocity: Vec2::X,
+                subparticles: Vec::new(),
+            })],
+        };
+        let ron = ron::to_string(&boxed).unwrap();
+
+        let particle = ron::from_str::<Particle>(&ron).unwrap();
+
+        assert_eq!(particle.subparticles.len(), 1);
+        assert_eq!(particle.subparticles[0].kind, ParticleKind::Killer);
+        assert_eq!(particle.subparticles[0].radius, 10.0);
+        assert_eq!(particle.subparticles[0].initial_velocity, Vec2::X);
+    }
+
+    #[test]
+    fn split_all_particles_resolves_the_whole_tree() {
+        let mut app = test_app();
+        let leaf = Particle {
+            initial_velocity: Vec2::X,
+            ..default()
+        };
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                subparticles: vec![
+                    Particle {
+                        initial_velocity: Vec2::Y,
+                        subparticles: vec![leaf.clone(), leaf.clone()],
+                        ..default()
+                    },
+                    leaf,
+                ],
+                ..default()
+            },
+            spawn_with_invincible: true,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        split_all_particles(app.world_mut()).unwrap();
+
+        let world = app.world_mut();
+        assert_eq!(
+            world
+                .query_filtered::<(), With<Particle>>()
+                .iter(world)
+                .count(),
+            0
+        );
+    }
+
+    fn invincibility_collision_groups(particle_config: ParticleConfig) -> [CollisionGroups; 2] {
+        let mut app = test_app();
+        app.insert_resource(particle_config);
+        app.init_resource::<ParticleAssets>();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle::default(),
+            spawn_with_invincible: true,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+        let particle = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut()
+            .run_system_once(invincibility_added)
+            .unwrap();
+        let invincible = *app.world().get::<CollisionGroups>(particle).unwrap();
+
+        app.world_mut().entity_mut(particle).remove::<Invincible>();
+        app.world_mut().send_event(InvincibleRemoved(particle));
+        app.world_mut()
+            .run_system_once(invincibility_removed)
+            .unwrap();
+        let normal = *app.world().get::<CollisionGroups>(particle).unwrap();
+
+        [invincible, normal]
+    }
+
+    #[test]
+    fn killer_subparticles_kill_once_vulnerable() {
+        let mut app = test_app();
+        app.init_resource::<ParticleAssets>();
+        app.add_event::<CollisionEvent>();
+        app.add_event::<KillEvent>();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                subparticles: vec![Particle {
+                    kind: ParticleKind::Killer,
+                    ..default()
+                }],
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+        let original = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        app.world_mut().send_event(ParticleSplitEvent(original));
+        app.world_mut().run_system_once(split_particle).unwrap();
+        let killer = app
+            .world_mut()
+            .query_filtered::<Entity, With<Particle>>()
+            .single(app.world())
+            .unwrap();
+
+        // Subparticles spawn invincible, they can't kill before it wears off.
+        assert!(app.world().get::<Invincible>(killer).is_some());
+        assert!(app.world().get::<Killer>(killer).is_none());
+
+        app.world_mut().entity_mut(killer).remove::<Invincible>();
+        app.world_mut().send_event(InvincibleRemoved(killer));
+        app.world_mut()
+            .run_system_once(invincibility_removed)
+            .unwrap();
+        assert!(app.world().get::<Killer>(killer).is_some());
+        assert!(app.world().get::<KillerLook>(killer).is_some());
+
+        let player = app
+            .world_mut()
+            .spawn((Player::default(), RigidBody::Dynamic))
+            .id();
+        app.world_mut().send_event(CollisionEvent::Started(
+            killer,
+            player,
+            CollisionEventFlags::empty(),
+        ));
+        app.world_mut()
+            .run_system_once(killer_collision_handler)
+            .unwrap();
+
+        let events = app.world().resource::<Events<KillEvent>>();
+        let killed: Vec<_> = events
+            .iter_current_update_events()
+            .map(|e| e.player)
+            .collect();
+        assert_eq!(killed, [player]);
+    }
+
+    #[test]
+    fn invincible_particles_switch_collision_groups() {
+        let groups = ParticleCollisionGroups::default();
+        assert_eq!(
+            invincibility_collision_groups(ParticleConfig::default()),
+            [groups.invincible, groups.normal]
+        );
+
+        let pass_through = ParticleConfig {
+            invincible_collides_with_player: false,
+            ..default()
+        };
+        assert_eq!(
+            invincibility_collision_groups(pass_through),
+            [groups.normal, groups.normal]
+        );
+    }
+
+    #[test]
+    fn spawn_velocity_scales_with_the_speed_scale() {
+        let mut app = test_app();
+        app.insert_resource(ParticleSpeedScale(0.5));
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                initial_velocity: vec2(400.0, 0.0),
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let (particle, velocity) = app
+            .world_mut()
+            .query::<(&Particle, &Velocity)>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(velocity.linvel, vec2(200.0, 0.0));
+        assert_eq!(particle.initial_velocity, vec2(400.0, 0.0));
+
+        let hard = ParticleSpeedScale(2.0);
+        assert_eq!(hard.apply(vec2(400.0, 0.0), 600.0), vec2(600.0, 0.0));
+        assert_eq!(hard.apply(vec2(800.0, 0.0), 600.0), vec2(800.0, 0.0));
+    }
+
+    #[test]
+    fn unscaled_velocities_scale_back() {
+        let hard = ParticleSpeedScale(1.25);
+        let velocity = vec2(300.0, -100.0);
+        assert_eq!(hard.unscale(velocity), vec2(240.0, -80.0));
+        assert_eq!(hard.apply(hard.unscale(velocity), 600.0), velocity);
+    }
+
+    #[test]
+    fn particles_spawn_with_their_angular_velocity() {
+        let mut app = test_app();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                initial_angvel: 2.5,
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let velocity = app
+            .world_mut()
+            .query::<&Velocity>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(velocity.angvel, 2.5);
+    }
+
+    #[test]
+    fn pop_sounds_are_capped_per_frame() {
+        let mut app = test_app();
+        app.init_resource::<ParticleAssets>();
+        let max_pops = app.world().resource::<ParticleConfig>().max_pops_per_frame;
+
+        let pop_count = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<(), With<SoundEffect>>()
+                .iter(app.world())
+                .count()
+        };
+        let collide = |app: &mut App| {
+            for _ in 0..3 * max_pops {
+                let particle1 = app.world_mut().spawn_empty().id();
+                let particle2 = app.world_mut().spawn_empty().id();
+                app.world_mut().trigger(ParticleParticleCollisionEvent {
+                    particle1,
+                    particle2,
+                });
+            }
+            app.world_mut().flush();
+        };
+
+        collide(&mut app);
+        assert_eq!(pop_count(&mut app), max_pops);
+
+        app.world_mut().run_system_once(reset_pop_sounds).unwrap();
+        app.world_mut().resource_mut::<PopSounds>().muted = true;
+        collide(&mut app);
+        assert_eq!(pop_count(&mut app), max_pops);
+    }
+
+    #[test]
+    fn only_fast_particles_use_ccd() {
+        let mut app = test_app();
+        app.insert_resource(ParticleConfig {
+            ccd: ParticleCcd::AboveSpeed { min_speed: 300.0 },
+            ..default()
+        });
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                initial_velocity: vec2(400.0, 0.0),
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let ccd_enabled = |app: &mut App| {
+            app.world_mut()
+                .query::<&Ccd>()
+                .single(app.world())
+                .unwrap()
+                .enabled
+        };
+        assert!(ccd_enabled(&mut app));
+
+        app.world_mut()
+            .query::<&mut Velocity>()
+            .single_mut(app.world_mut())
+            .unwrap()
+            .linvel = vec2(100.0, 0.0);
+        app.world_mut()
+            .run_system_once(update_particle_ccd)
+            .unwrap();
+        assert!(!ccd_enabled(&mut app));
+    }
+
+    /// Where a particle fired at a thin wall 150 pixels ahead, a particle diameter and a half in a
+    /// frame, ends up.
+    fn fire_at_thin_wall(ccd: ParticleCcd) -> f32 {
+        let mut app = physics_app();
+        app.insert_resource(ParticleConfig { ccd, ..default() });
+
+        app.world_mut().spawn((
+            Transform::from_xyz(150.0, 0.0, 0.0),
+            RigidBody::Fixed,
+            Collider::cuboid(1.0, 200.0),
+            CollisionGroups::new(Group::GROUP_1, Group::all()),
+        ));
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                initial_velocity: vec2(6000.0, 0.0),
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        app.world_mut()
+            .query_filtered::<&Transform, With<Particle>>()
+            .single(app.world())
+            .unwrap()
+            .translation
+            .x
+    }
+
+    #[test]
+    fn ccd_keeps_fast_particles_from_passing_through_thin_walls() {
+        assert!(fire_at_thin_wall(ParticleCcd::Always) < 150.0);
+        // Otherwise the test could pass without CCD doing anything.
+        assert!(fire_at_thin_wall(ParticleCcd::Never) > 150.0);
     }
 }
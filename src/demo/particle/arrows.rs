@@ -1,10 +1,17 @@
+use std::time::Duration;
+
 use bevy::{
     ecs::{relationship::RelatedSpawner, spawn::SpawnWith},
     prelude::*,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{AppSystems, PausableSystems, asset_tracking::LoadResource};
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::LoadResource,
+    demo::{level::SpawnRawLevel, player::PlayerLaunched},
+    screens::Screen,
+};
 
 use super::Particle;
 
@@ -12,11 +19,13 @@ pub(super) fn plugin(app: &mut App) {
     app.register_type::<Arrows>();
 
     app.init_resource::<ArrowsConfig>();
+    app.init_resource::<ArrowsFadeState>();
     app.load_resource::<ArrowsAssets>();
 
+    app.add_observer(reset_arrows_fade);
     app.add_systems(
         Update,
-        move_arrows
+        (move_arrows, fade_arrows.run_if(in_state(Screen::Gameplay)))
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
@@ -28,6 +37,13 @@ pub struct ArrowsConfig {
     arrow_offset: f32,
     arrow_scale: f32,
     local_z: f32,
+    /// Tints each arrow with the color of its subparticle.
+    pub tint: bool,
+    /// Scales each arrow with the speed of its subparticle, see [`ArrowsConfig::scale`].
+    pub speed_scale: Option<ArrowsSpeedScale>,
+    pub fade: ArrowsFade,
+    /// How long the arrows take to fade out once they start fading.
+    pub fade_duration: Duration,
 }
 
 impl Default for ArrowsConfig {
@@ -36,6 +52,107 @@ impl Default for ArrowsConfig {
             arrow_offset: 3.0,
             arrow_scale: 0.02,
             local_z: -3.0,
+            tint: true,
+            speed_scale: Some(ArrowsSpeedScale::default()),
+            fade: ArrowsFade::AfterLaunch,
+            fade_duration: Duration::from_secs_f32(0.5),
+        }
+    }
+}
+
+impl ArrowsConfig {
+    /// The scale of the arrow of a subparticle moving at `speed`.
+    pub fn scale(&self, speed: f32) -> f32 {
+        match self.speed_scale {
+            Some(speed_scale) => self.arrow_scale * speed_scale.factor(speed),
+            None => self.arrow_scale,
+        }
+    }
+}
+
+/// Arrows of subparticles at `reference_speed` keep their size, faster ones are bigger.
+#[derive(Serialize, Deserialize, Reflect, Clone, Copy, PartialEq, Debug)]
+pub struct ArrowsSpeedScale {
+    pub reference_speed: f32,
+    pub min_factor: f32,
+    pub max_factor: f32,
+}
+
+impl Default for ArrowsSpeedScale {
+    fn default() -> Self {
+        Self {
+            reference_speed: 500.0,
+            min_factor: 0.5,
+            max_factor: 2.0,
+        }
+    }
+}
+
+impl ArrowsSpeedScale {
+    fn factor(&self, speed: f32) -> f32 {
+        (speed / self.reference_speed.max(f32::EPSILON)).clamp(self.min_factor, self.max_factor)
+    }
+}
+
+/// When the arrows of a level start fading out in gameplay, they stay hidden until a restart.
+#[derive(Serialize, Deserialize, Reflect, Clone, Copy, PartialEq, Debug)]
+pub enum ArrowsFade {
+    Never,
+    /// After the first launch of the player.
+    AfterLaunch,
+    /// After the level was spawned for this long.
+    AfterDelay(Duration),
+}
+
+#[derive(Resource)]
+struct ArrowsFadeState {
+    since_spawn: Duration,
+    fading: bool,
+    alpha: f32,
+}
+
+impl Default for ArrowsFadeState {
+    fn default() -> Self {
+        Self {
+            since_spawn: Duration::ZERO,
+            fading: false,
+            alpha: 1.0,
+        }
+    }
+}
+
+/// Spawning a level, including restarts, brings the arrows back.
+fn reset_arrows_fade(_: Trigger<SpawnRawLevel>, mut fade_state: ResMut<ArrowsFadeState>) {
+    *fade_state = ArrowsFadeState::default();
+}
+
+fn fade_arrows(
+    mut launch_events: EventReader<PlayerLaunched>,
+    mut fade_state: ResMut<ArrowsFadeState>,
+    arrows_config: Res<ArrowsConfig>,
+    time: Res<Time>,
+    arrows_query: Query<&Children, With<ArrowsOf>>,
+    mut sprite_query: Query<&mut Sprite>,
+) {
+    let launched = launch_events.read().count() > 0;
+
+    fade_state.since_spawn += time.delta();
+    fade_state.fading |= match arrows_config.fade {
+        ArrowsFade::Never => false,
+        ArrowsFade::AfterLaunch => launched,
+        ArrowsFade::AfterDelay(delay) => fade_state.since_spawn >= delay,
+    };
+
+    if fade_state.fading {
+        let step = time.delta_secs() / arrows_config.fade_duration.as_secs_f32().max(f32::EPSILON);
+        fade_state.alpha = (fade_state.alpha - step).max(0.0);
+    }
+
+    // Arrows of new subparticles start out opaque, so this is applied every frame.
+    for children in arrows_query.iter() {
+        let mut sprites = sprite_query.iter_many_mut(children);
+        while let Some(mut sprite) = sprites.fetch_next() {
+            sprite.color.set_alpha(fade_state.alpha);
         }
     }
 }
@@ -84,13 +201,22 @@ pub fn arrows(
             let offset = particle.radius + arrows_config.arrow_offset;
             let position = Vec2::ZERO + direction * offset;
 
+            let color = if arrows_config.tint {
+                sub_particle.color
+            } else {
+                Color::WHITE
+            };
+
             parent.spawn((
                 Name::new("Arrow"),
-                Sprite::from_image(arrow_image.clone()),
+                Sprite {
+                    color,
+                    ..Sprite::from_image(arrow_image.clone())
+                },
                 Transform {
                     translation: position.extend(0.0),
                     rotation: Quat::from_rotation_z(angle),
-                    scale: Vec3::ONE * arrows_config.arrow_scale,
+                    scale: Vec3::ONE * arrows_config.scale(sub_particle.initial_velocity.length()),
                 },
             ));
         }
@@ -114,3 +240,37 @@ fn move_arrows(
         arrows_transform.translation = transform.translation.xy().extend(arrows_config.local_z);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_scale_with_speed_within_bounds() {
+        let config = ArrowsConfig::default();
+        let speed_scale = config.speed_scale.unwrap();
+
+        assert_eq!(
+            config.scale(speed_scale.reference_speed),
+            config.arrow_scale
+        );
+        assert_eq!(
+            config.scale(1.5 * speed_scale.reference_speed),
+            1.5 * config.arrow_scale
+        );
+        assert_eq!(
+            config.scale(0.0),
+            speed_scale.min_factor * config.arrow_scale
+        );
+        assert_eq!(
+            config.scale(100.0 * speed_scale.reference_speed),
+            speed_scale.max_factor * config.arrow_scale
+        );
+
+        let fixed = ArrowsConfig {
+            speed_scale: None,
+            ..config
+        };
+        assert_eq!(fixed.scale(0.0), fixed.arrow_scale);
+    }
+}
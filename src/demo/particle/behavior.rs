@@ -0,0 +1,85 @@
+//! Kind specific particle behavior, dispatched through components.
+//!
+//! Every [`ParticleKind`] with a behavior gets that behavior's components while it isn't
+//! invincible, e.g. [`Killer`] for killer particles. Other crates can add their own kinds with
+//! [`RegisterParticleKind::register_particle_kind`] and handle the components in their own systems,
+//! the same way the killer is handled.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use super::ParticleKind;
+use crate::demo::killer::Killer;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ParticleBehaviors>();
+    app.register_particle_behavior(ParticleKind::Killer, ComponentBehavior(Killer));
+}
+
+/// What makes a [`ParticleKind`] different from a normal particle.
+pub trait ParticleBehavior: Send + Sync + 'static {
+    /// Adds the components of the behavior to a particle.
+    fn insert(&self, entity: &mut EntityCommands);
+    /// Removes what [`ParticleBehavior::insert`] added.
+    fn remove(&self, entity: &mut EntityCommands);
+}
+
+/// A behavior that is a single component, removed together with the components it requires.
+pub struct ComponentBehavior<C>(pub C);
+
+impl<C: Component + Clone> ParticleBehavior for ComponentBehavior<C> {
+    fn insert(&self, entity: &mut EntityCommands) {
+        entity.insert(self.0.clone());
+    }
+
+    fn remove(&self, entity: &mut EntityCommands) {
+        entity.remove_with_requires::<C>();
+    }
+}
+
+/// The behaviors of all particle kinds, normal particles have none.
+#[derive(Resource, Default)]
+pub struct ParticleBehaviors(HashMap<ParticleKind, Box<dyn ParticleBehavior>>);
+
+impl ParticleBehaviors {
+    pub fn get(&self, kind: &ParticleKind) -> Option<&dyn ParticleBehavior> {
+        self.0.get(kind).map(Box::as_ref)
+    }
+}
+
+pub trait RegisterParticleKind {
+    /// Gives particles of kind [`ParticleKind::Custom`] with this `name` the `behavior`.
+    fn register_particle_kind(
+        &mut self,
+        name: impl Into<String>,
+        behavior: impl ParticleBehavior,
+    ) -> &mut Self;
+
+    /// Gives particles of any `kind` the `behavior`, replacing the one it had.
+    fn register_particle_behavior(
+        &mut self,
+        kind: ParticleKind,
+        behavior: impl ParticleBehavior,
+    ) -> &mut Self;
+}
+
+impl RegisterParticleKind for App {
+    fn register_particle_kind(
+        &mut self,
+        name: impl Into<String>,
+        behavior: impl ParticleBehavior,
+    ) -> &mut Self {
+        self.register_particle_behavior(ParticleKind::Custom(name.into()), behavior)
+    }
+
+    fn register_particle_behavior(
+        &mut self,
+        kind: ParticleKind,
+        behavior: impl ParticleBehavior,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ParticleBehaviors>()
+            .0
+            .insert(kind, Box::new(behavior));
+        self
+    }
+}
@@ -0,0 +1,80 @@
+//! Particles that split on their own after a delay, for timed puzzles.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{ParticleSplitEvent, invincible::Invincible};
+use crate::{AppSystems, PausableSystems, demo::time_scale::Game};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        burn_fuses
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Splits the particle once a duration of physics time has passed, see [`super::Particle::fuse`].
+#[derive(Component)]
+pub struct Fuse(Timer);
+
+impl Fuse {
+    pub fn new(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
+    }
+}
+
+/// Fuses only burn once the particle can be split, so subparticles get their full delay.
+fn burn_fuses(
+    time: Res<Time<Game>>,
+    mut query: Query<(Entity, &mut Fuse), Without<Invincible>>,
+    mut split_events: EventWriter<ParticleSplitEvent>,
+) {
+    for (entity, mut fuse) in query.iter_mut() {
+        fuse.0.tick(time.delta());
+
+        if fuse.0.just_finished() {
+            split_events.write(ParticleSplitEvent(entity));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::demo::particle::{Particle, SpawnParticle, headless_app, split_particle};
+
+    #[test]
+    fn fused_particles_split_without_a_collision() {
+        let mut app = headless_app();
+        app.init_resource::<Time<Game>>();
+
+        app.world_mut().trigger(SpawnParticle {
+            translation: Vec2::ZERO,
+            particle: Particle {
+                fuse: Some(Duration::from_secs(1)),
+                subparticles: vec![Particle::default(), Particle::default()],
+                ..default()
+            },
+            spawn_with_invincible: false,
+            show_arrows: false,
+            parent: None,
+        });
+        app.world_mut().flush();
+
+        let mut particle_query = app.world_mut().query_filtered::<(), With<Particle>>();
+        for expected in [1, 2] {
+            app.world_mut()
+                .resource_mut::<Time<Game>>()
+                .advance_by(Duration::from_millis(600));
+            app.world_mut().run_system_once(burn_fuses).unwrap();
+            app.world_mut().run_system_once(split_particle).unwrap();
+
+            assert_eq!(particle_query.iter(app.world()).count(), expected);
+        }
+    }
+}
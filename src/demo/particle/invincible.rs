@@ -3,7 +3,7 @@ use std::time::Duration;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{AppSystems, PausableSystems};
+use crate::{AppSystems, PausableSystems, demo::time_scale::Game};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<InvincibleRemoved>();
@@ -15,6 +15,8 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// Protects a particle from being split for a duration of physics time,
+/// so it lasts longer in real time while the game is slowed down.
 #[derive(Component, Serialize, Deserialize)]
 pub struct Invincible(Timer);
 
@@ -28,7 +30,7 @@ impl Invincible {
 pub struct InvincibleRemoved(pub Entity);
 
 fn tick_invincibility(
-    time: Res<Time>,
+    time: Res<Time<Game>>,
     mut query: Query<(Entity, &mut Invincible)>,
     mut events: EventWriter<InvincibleRemoved>,
     mut commands: Commands,
@@ -42,3 +44,44 @@ fn tick_invincibility(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_rapier2d::plugin::TimestepMode;
+
+    use super::*;
+    use crate::demo::time_scale::update_game_time;
+
+    #[test]
+    fn invincibility_lasts_in_physics_time() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, plugin));
+        app.init_resource::<Time<Game>>();
+        app.add_systems(Update, update_game_time.before(tick_invincibility));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            0.1,
+        )));
+        app.insert_resource(TimestepMode::Variable {
+            max_dt: 1.0 / 60.0,
+            time_scale: 0.1,
+            substeps: 1,
+        });
+
+        let entity = app
+            .world_mut()
+            .spawn(Invincible::new(Duration::from_secs_f32(0.5)))
+            .id();
+
+        // 4 seconds of real time are only 0.4 seconds of physics time.
+        for _ in 0..40 {
+            app.update();
+        }
+        assert!(app.world().get::<Invincible>(entity).is_some());
+
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(app.world().get::<Invincible>(entity).is_none());
+    }
+}
@@ -0,0 +1,128 @@
+//! Particle shapes other than balls, for levels with less predictable bounces.
+//!
+//! The collider and the mesh of a shape are built from the same outline, so what is drawn is
+//! what collides.
+
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
+use bevy_rapier2d::prelude::Collider;
+use serde::{Deserialize, Serialize};
+
+/// Segments of each rounded corner of [`ParticleShape::RoundedRectangle`].
+const CORNER_SEGMENTS: usize = 4;
+
+/// The shape of a particle, sized by [`super::Particle::radius`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ParticleShape {
+    #[default]
+    Ball,
+    /// A regular polygon inside of the radius, with a corner at the top.
+    Polygon { sides: u32 },
+    /// A rectangle as wide as the radius on each side, `corner_radius` being clamped to fit.
+    RoundedRectangle {
+        half_height: f32,
+        corner_radius: f32,
+    },
+}
+
+impl ParticleShape {
+    pub fn collider(&self, radius: f32) -> Collider {
+        self.outline(radius)
+            .and_then(Collider::convex_polyline)
+            .unwrap_or_else(|| Collider::ball(radius))
+    }
+
+    pub fn mesh(&self, radius: f32) -> Mesh {
+        let Some(outline) = self.outline(radius) else {
+            return Circle::new(radius).into();
+        };
+
+        // The outline is convex, so a fan around the first point covers it.
+        let indices = (1..outline.len() as u32 - 1)
+            .flat_map(|i| [0, i, i + 1])
+            .collect();
+        let positions: Vec<_> = outline.into_iter().map(|point| point.extend(0.0)).collect();
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// The points around the shape in counterclockwise order, `None` for balls.
+    fn outline(&self, radius: f32) -> Option<Vec<Vec2>> {
+        match *self {
+            Self::Ball => None,
+            Self::Polygon { sides } => {
+                let sides = sides.max(3);
+                Some(
+                    (0..sides)
+                        .map(|i| {
+                            let angle = FRAC_PI_2 + i as f32 * TAU / sides as f32;
+                            radius * Vec2::from_angle(angle)
+                        })
+                        .collect(),
+                )
+            }
+            Self::RoundedRectangle {
+                half_height,
+                corner_radius,
+            } => {
+                let half_size = vec2(radius, half_height.max(0.0));
+                let corner_radius = corner_radius.clamp(0.0, half_size.min_element());
+                let inner = half_size - corner_radius;
+
+                let corners = [
+                    (inner, 0.0),
+                    (vec2(-inner.x, inner.y), FRAC_PI_2),
+                    (-inner, 2.0 * FRAC_PI_2),
+                    (vec2(inner.x, -inner.y), 3.0 * FRAC_PI_2),
+                ];
+                Some(
+                    corners
+                        .into_iter()
+                        .flat_map(|(center, start)| {
+                            (0..=CORNER_SEGMENTS).map(move |i| {
+                                let angle = start + FRAC_PI_2 * i as f32 / CORNER_SEGMENTS as f32;
+                                center + corner_radius * Vec2::from_angle(angle)
+                            })
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlines_stay_within_their_size() {
+        let polygon = ParticleShape::Polygon { sides: 5 }.outline(20.0).unwrap();
+        assert_eq!(polygon.len(), 5);
+        assert!(polygon[0].abs_diff_eq(vec2(0.0, 20.0), 1e-4));
+
+        let rectangle = ParticleShape::RoundedRectangle {
+            half_height: 10.0,
+            corner_radius: 50.0,
+        }
+        .outline(20.0)
+        .unwrap();
+        assert!(
+            rectangle
+                .iter()
+                .all(|point| point.x.abs() <= 20.0 + 1e-4 && point.y.abs() <= 10.0 + 1e-4)
+        );
+
+        assert!(ParticleShape::Ball.outline(20.0).is_none());
+    }
+}
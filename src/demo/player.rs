@@ -14,35 +14,66 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use super::{
-    drag_input::StretchInputEvent,
+    drag_input::{StretchInputEvent, StretchTooShortEvent},
     time_scale::{SetTimeScale, SetTimeScaleOverride, TimeScaleKind},
 };
 use crate::{
-    AppSystems, PausableSystems, asset_tracking::LoadResource, audio::sound_effect, screens::Screen,
+    AppSystems, PausableSystems, asset_tracking::LoadResource, audio::sound_effect,
+    key_bindings::KeyBindings, screens::Screen, settings::Settings,
 };
 
-pub(super) fn plugin(app: &mut App) {
-    app.register_type::<Player>();
+/// The player character and its drag controls.
+///
+/// Can be added by other crates with a custom [`PlayerConfig`].
+#[derive(Default)]
+pub struct PlayerPlugin {
+    pub config: PlayerConfig,
+}
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Player>();
+        app.register_type::<PlayerState>();
+
+        app.insert_resource(self.config.clone());
+        app.load_resource::<PlayerAssets>();
 
-    app.init_resource::<PlayerConfig>();
-    app.load_resource::<PlayerAssets>();
+        app.add_event::<PlayerLaunched>();
+        app.add_event::<FirstLaunch>();
 
-    app.add_systems(
-        Update,
-        (override_time_scale, handle_drag_input)
-            .in_set(AppSystems::Update)
-            .in_set(PausableSystems)
-            .run_if(in_state(Screen::Gameplay)),
-    );
+        app.add_systems(
+            Update,
+            (
+                override_time_scale,
+                handle_drag_input,
+                handle_drag_too_short,
+                settle_player,
+                shake_player,
+            )
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
 }
 
-#[derive(Resource, Reflect)]
+#[derive(Resource, Reflect, Clone)]
 #[reflect(Resource)]
 pub struct PlayerConfig {
     pub local_z: f32,
     pub radius: f32,
     pub color: Color,
     pub force_scalar: f32,
+    pub relaunch: Relaunch,
+}
+
+/// When a player that hit a particle can be launched again.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub enum Relaunch {
+    /// Right away, even while it is still flying fast.
+    OnParticleHit,
+    /// Once its speed dropped below `max_speed`.
+    WhenSettled { max_speed: f32 },
 }
 
 impl Default for PlayerConfig {
@@ -52,15 +83,18 @@ impl Default for PlayerConfig {
             radius: 20.0,
             color: Color::hsl(0.0, 0.95, 0.7),
             force_scalar: 7000.0,
+            relaunch: Relaunch::OnParticleHit,
         }
     }
 }
 
 #[derive(Asset, Resource, Clone, Reflect)]
 #[reflect(Resource)]
-struct PlayerAssets {
+pub(crate) struct PlayerAssets {
     #[dependency]
     shoot_sound: Handle<AudioSource>,
+    #[dependency]
+    fizzle_sound: Handle<AudioSource>,
 }
 
 impl FromWorld for PlayerAssets {
@@ -69,6 +103,7 @@ impl FromWorld for PlayerAssets {
 
         Self {
             shoot_sound: assets.load::<AudioSource>("audio/sound_effects/shoot.ogg"),
+            fizzle_sound: assets.load::<AudioSource>("audio/sound_effects/fizzle.ogg"),
         }
     }
 }
@@ -86,66 +121,139 @@ pub fn player(
     (
         Name::new("Player"),
         Transform::from_translation(translation.extend(0.0)),
-        Player { can_move: true },
-        Mesh2d(mesh),
-        MeshMaterial2d(material),
+        Player::default(),
+        Visibility::default(),
         (
             RigidBody::Dynamic,
             Ccd::enabled(),
             Sleeping::disabled(),
             Collider::ball(player_config.radius),
-            children![(
-                Name::new("Player Sensor"),
-                ActiveEvents::COLLISION_EVENTS,
-                CollisionGroups::new(Group::GROUP_3, Group::GROUP_3),
-                Collider::ball(player_config.radius),
-                Sensor
-            )],
+            children![
+                (
+                    Name::new("Player Sensor"),
+                    ActiveEvents::COLLISION_EVENTS,
+                    CollisionGroups::new(Group::GROUP_3, Group::GROUP_3),
+                    Collider::ball(player_config.radius),
+                    Sensor
+                ),
+                // The visual is a separate entity, so it can be shaken without moving the body.
+                (
+                    Name::new("Player Visual"),
+                    PlayerVisual,
+                    Mesh2d(mesh),
+                    MeshMaterial2d(material),
+                )
+            ],
             CollisionGroups::new(Group::GROUP_2, Group::GROUP_1 | Group::GROUP_2),
-            Restitution::coefficient(0.5),
+            // How much the player bounces is up to what it hits, see `level_bounds` and `obstacle`.
+            Restitution::coefficient(0.0),
             Velocity::default(),
             ExternalImpulse::default(),
         ),
     )
 }
 
-#[derive(Component, Reflect)]
+#[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct Player {
-    pub can_move: bool,
+    pub state: PlayerState,
+    /// Launches since the player spawned, so since the level (re)started.
+    pub launches: u32,
+}
+
+/// Sent whenever a drag actually launches the player.
+#[derive(Event)]
+pub struct PlayerLaunched {
+    pub player: Entity,
+    pub vector: Vec2,
+}
+
+/// Sent together with the first [`PlayerLaunched`] of a level, again after a restart.
+#[derive(Event)]
+pub struct FirstLaunch {
+    pub player: Entity,
+}
+
+impl Player {
+    /// Whether the player can be launched right now, UI hinting at a launch should check this.
+    pub fn can_move(&self) -> bool {
+        self.state == PlayerState::Ready
+    }
+
+    /// Readies a launched player again after it hit a particle, possibly after it settled.
+    pub fn hit_particle(&mut self, relaunch: Relaunch) {
+        if self.state != PlayerState::Launched {
+            return;
+        }
+
+        self.state = match relaunch {
+            Relaunch::OnParticleHit => PlayerState::Ready,
+            Relaunch::WhenSettled { .. } => PlayerState::Settling,
+        };
+    }
+}
+
+/// The launch cycle of the player.
+///
+/// A launch moves the player from [`PlayerState::Ready`] to [`PlayerState::Launched`]. Hitting a
+/// particle that isn't invincible makes it ready again, by default right away so chains of launches
+/// are possible without waiting for the player to stop, see [`Relaunch`]. Ending the level
+/// finishes it for good.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerState {
+    #[default]
+    Ready,
+    Launched,
+    /// Hit a particle, waiting to slow down before it is ready again.
+    Settling,
+    Finished,
 }
 
+#[derive(Component)]
+struct PlayerVisual;
+
+const SHAKE_DURATION_SECS: f32 = 0.2;
+const SHAKE_AMPLITUDE: f32 = 3.0;
+const SHAKE_FREQUENCY: f32 = 25.0;
+
+/// Shakes the player visual from side to side until the timer finishes.
+#[derive(Component)]
+struct Shake(Timer);
+
 fn override_time_scale(
     input: Res<ButtonInput<MouseButton>>,
+    key_bindings: Res<KeyBindings>,
     query: Query<Entity, With<Player>>,
     mut time_override_events: EventWriter<SetTimeScaleOverride>,
 ) {
     if !query.is_empty() {
-        if input.just_pressed(MouseButton::Right) {
+        if input.just_pressed(key_bindings.normal_speed) {
             time_override_events.write(SetTimeScaleOverride(Some(TimeScaleKind::Normal)));
         }
 
-        if input.just_released(MouseButton::Right) {
+        if input.just_released(key_bindings.normal_speed) {
             time_override_events.write(SetTimeScaleOverride(None));
         }
     }
 }
 
-fn handle_drag_input(
+pub(crate) fn handle_drag_input(
     mut events: EventReader<StretchInputEvent>,
-    mut query: Query<(&mut Player, &mut ExternalImpulse, &mut Velocity)>,
+    mut query: Query<(Entity, &mut Player, &mut ExternalImpulse, &mut Velocity)>,
     player_config: Res<PlayerConfig>,
     player_assets: Res<PlayerAssets>,
     mut time_events: EventWriter<SetTimeScale>,
+    mut launched_events: EventWriter<PlayerLaunched>,
+    mut first_launch_events: EventWriter<FirstLaunch>,
     mut commands: Commands,
 ) {
     if query.is_empty() {
         return;
     }
 
-    let (mut player, mut external_impulse, mut velocity) = query.single_mut().unwrap();
+    let (entity, mut player, mut external_impulse, mut velocity) = query.single_mut().unwrap();
 
-    if !player.can_move {
+    if !player.can_move() {
         return;
     }
 
@@ -155,8 +263,187 @@ fn handle_drag_input(
 
         commands.spawn(sound_effect(player_assets.shoot_sound.clone()));
 
-        player.can_move = false;
+        player.state = PlayerState::Launched;
 
         time_events.write(SetTimeScale(TimeScaleKind::Normal));
+
+        if player.launches == 0 {
+            first_launch_events.write(FirstLaunch { player: entity });
+        }
+        player.launches += 1;
+        launched_events.write(PlayerLaunched {
+            player: entity,
+            vector: event.vector,
+        });
+    }
+}
+
+fn settle_player(mut query: Query<(&mut Player, &Velocity)>, player_config: Res<PlayerConfig>) {
+    let Relaunch::WhenSettled { max_speed } = player_config.relaunch else {
+        return;
+    };
+
+    for (mut player, velocity) in query.iter_mut() {
+        if player.state == PlayerState::Settling && velocity.linvel.length() < max_speed {
+            player.state = PlayerState::Ready;
+        }
+    }
+}
+
+/// Communicates that the drag was too short to shoot.
+fn handle_drag_too_short(
+    mut events: EventReader<StretchTooShortEvent>,
+    player_query: Query<&Player>,
+    visual_query: Query<Entity, With<PlayerVisual>>,
+    player_assets: Res<PlayerAssets>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    if events.read().last().is_none() {
+        return;
+    }
+
+    let Ok(player) = player_query.single() else {
+        return;
+    };
+    if !player.can_move() {
+        return;
+    }
+
+    commands.spawn(sound_effect(player_assets.fizzle_sound.clone()));
+    if settings.reduce_motion {
+        return;
+    }
+
+    for visual in visual_query.iter() {
+        commands.entity(visual).insert(Shake(Timer::from_seconds(
+            SHAKE_DURATION_SECS,
+            TimerMode::Once,
+        )));
+    }
+}
+
+fn shake_player(
+    mut query: Query<(Entity, &mut Shake, &mut Transform)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut shake, mut transform) in query.iter_mut() {
+        shake.0.tick(time.delta());
+
+        if shake.0.finished() {
+            transform.translation.x = 0.0;
+            commands.entity(entity).remove::<Shake>();
+            continue;
+        }
+
+        let elapsed = shake.0.elapsed_secs();
+        let falloff = shake.0.fraction_remaining();
+        transform.translation.x =
+            SHAKE_AMPLITUDE * falloff * (elapsed * SHAKE_FREQUENCY * std::f32::consts::TAU).sin();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn hit_launched_player(relaunch: Relaunch, linvel: Vec2) -> (World, Entity) {
+        let mut world = World::new();
+        world.insert_resource(PlayerConfig {
+            relaunch,
+            ..default()
+        });
+
+        let mut player = Player {
+            state: PlayerState::Launched,
+            ..default()
+        };
+        player.hit_particle(relaunch);
+        let entity = world
+            .spawn((
+                player,
+                Velocity {
+                    linvel,
+                    angvel: 0.0,
+                },
+            ))
+            .id();
+
+        (world, entity)
+    }
+
+    #[test]
+    fn player_is_ready_right_after_a_hit() {
+        let (world, player) = hit_launched_player(Relaunch::OnParticleHit, Vec2::new(1000.0, 0.0));
+
+        assert!(world.get::<Player>(player).unwrap().can_move());
+    }
+
+    #[test]
+    fn player_is_ready_once_settled() {
+        let (mut world, player) = hit_launched_player(
+            Relaunch::WhenSettled { max_speed: 50.0 },
+            Vec2::new(1000.0, 0.0),
+        );
+
+        world.run_system_once(settle_player).unwrap();
+        assert_eq!(
+            world.get::<Player>(player).unwrap().state,
+            PlayerState::Settling
+        );
+
+        world.get_mut::<Velocity>(player).unwrap().linvel = Vec2::new(10.0, 0.0);
+        world.run_system_once(settle_player).unwrap();
+        assert!(world.get::<Player>(player).unwrap().can_move());
+    }
+
+    #[test]
+    fn first_launch_is_sent_once_per_spawned_player() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<AudioSource>();
+        app.init_resource::<PlayerAssets>();
+        app.init_resource::<PlayerConfig>();
+        app.add_event::<StretchInputEvent>();
+        app.add_event::<SetTimeScale>();
+        app.add_event::<PlayerLaunched>();
+        app.add_event::<FirstLaunch>();
+
+        let spawn_player = |world: &mut World| {
+            world
+                .spawn((
+                    Player::default(),
+                    ExternalImpulse::default(),
+                    Velocity::default(),
+                ))
+                .id()
+        };
+        let launch = |world: &mut World| {
+            world.send_event(StretchInputEvent { vector: Vec2::X });
+            world.run_system_once(handle_drag_input).unwrap();
+        };
+
+        let world = app.world_mut();
+        let first_player = spawn_player(world);
+        for _ in 0..2 {
+            launch(world);
+            world.get_mut::<Player>(first_player).unwrap().state = PlayerState::Ready;
+        }
+
+        // Restarting respawns the player together with the level.
+        world.despawn(first_player);
+        let second_player = spawn_player(world);
+        launch(world);
+
+        assert_eq!(world.resource::<Events<PlayerLaunched>>().len(), 3);
+        let first_launches: Vec<_> = world
+            .resource::<Events<FirstLaunch>>()
+            .iter_current_update_events()
+            .map(|event| event.player)
+            .collect();
+        assert_eq!(first_launches, [first_player, second_player]);
     }
 }
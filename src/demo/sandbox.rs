@@ -0,0 +1,277 @@
+//! A free-play sandbox, a level without an objective where particles are spawned by ctrl clicking.
+//!
+//! It is played on [`Screen::Gameplay`] while the [`Sandbox`] resource exists, so the player and
+//! the particles behave exactly like in the levels.
+
+use bevy::prelude::*;
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContextPass, EguiContexts},
+    egui,
+};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    camera::{GameplayCamera, Letterboxing},
+    demo::{
+        difficulty::apply_slow_motion,
+        drag_input::DragInputController,
+        editor::{mouse_world_position, particle_ui, vec2_input_ui},
+        level::{PlaySource, RawLevel, SpawnRawLevel, level_data::LevelData, objective::Objective},
+        particle::{Particle, SpawnParticle},
+        time_scale::{SetTimeScaleOverride, TimeScaleConfig},
+    },
+    key_bindings::KeyBindings,
+    screens::{Screen, gameplay::SelectedLevel},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(start_sandbox);
+
+    app.add_systems(
+        EguiContextPass,
+        sandbox_ui.run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Pause(false)))
+                .and(resource_exists::<Sandbox>),
+        ),
+    );
+    app.add_systems(
+        Update,
+        (
+            apply_sandbox_physics.run_if(resource_changed::<Sandbox>),
+            (ignore_drags_over_panel, spawn_sandbox_particle).in_set(PausableSystems),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<Sandbox>)),
+    );
+    // The slow motion may have been changed from the pause menu while in the sandbox.
+    app.add_systems(
+        OnExit(Screen::Gameplay),
+        (end_sandbox, apply_slow_motion)
+            .chain()
+            .run_if(resource_exists::<Sandbox>),
+    );
+}
+
+/// Starts the sandbox, entering [`Screen::Gameplay`].
+#[derive(Event)]
+pub struct StartSandbox;
+
+/// The sandbox being played, removed when leaving gameplay.
+#[derive(Resource)]
+pub struct Sandbox {
+    /// Spawned where the sandbox is ctrl clicked.
+    pub particle: Particle,
+    pub gravity: Vec2,
+    /// Scales both the normal and the slowed physics speed.
+    pub time_scale: f32,
+    /// Restored when the sandbox ends.
+    initial_gravity: Vec2,
+    initial_time_scale: TimeScaleConfig,
+}
+
+const MAX_TIME_SCALE: f32 = 3.0;
+
+/// An empty level that never ends.
+fn sandbox_level() -> LevelData {
+    LevelData {
+        name: String::from("Sandbox"),
+        objective: Objective::FreePlay,
+        ..default()
+    }
+}
+
+fn start_sandbox(
+    _: Trigger<StartSandbox>,
+    rapier_config: Single<&RapierConfiguration>,
+    time_scale_config: Res<TimeScaleConfig>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    commands.insert_resource(Sandbox {
+        particle: Particle::default(),
+        gravity: rapier_config.gravity,
+        time_scale: 1.0,
+        initial_gravity: rapier_config.gravity,
+        initial_time_scale: time_scale_config.clone(),
+    });
+
+    commands.trigger(SpawnRawLevel {
+        data: sandbox_level(),
+        level: None,
+        source: PlaySource::Sandbox,
+    });
+    selected_level.0 = None;
+    next_screen.set(Screen::Gameplay);
+}
+
+fn end_sandbox(
+    sandbox: Res<Sandbox>,
+    mut rapier_config: Single<&mut RapierConfiguration>,
+    mut commands: Commands,
+) {
+    rapier_config.gravity = sandbox.initial_gravity;
+    commands.insert_resource(sandbox.initial_time_scale.clone());
+    commands.remove_resource::<Sandbox>();
+}
+
+fn sandbox_ui(mut contexts: EguiContexts, mut sandbox: ResMut<Sandbox>) {
+    egui::Window::new("Sandbox")
+        .default_pos([10.0, 10.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label("Ctrl + click to spawn a particle.");
+
+            egui::Grid::new("sandbox_grid")
+                .num_columns(2)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    // Only changed values are written, so the physics is applied on change.
+                    ui.label("Gravity:");
+                    let mut gravity = sandbox.gravity;
+                    vec2_input_ui(ui, &mut gravity);
+                    if gravity != sandbox.gravity {
+                        sandbox.gravity = gravity;
+                    }
+                    ui.end_row();
+
+                    ui.label("Time Scale:");
+                    let mut time_scale = sandbox.time_scale;
+                    ui.add(egui::Slider::new(&mut time_scale, 0.1..=MAX_TIME_SCALE));
+                    if time_scale != sandbox.time_scale {
+                        sandbox.time_scale = time_scale;
+                    }
+                    ui.end_row();
+                });
+
+            // The template isn't applied to anything, editing it isn't a change of the physics.
+            let particle = &mut sandbox.bypass_change_detection().particle;
+            let mut velocity_target = Vec::new();
+            particle_ui(ui, true, 0, &[], particle, &mut velocity_target);
+        });
+}
+
+fn apply_sandbox_physics(
+    sandbox: Res<Sandbox>,
+    mut rapier_config: Single<&mut RapierConfiguration>,
+    mut time_scale_config: ResMut<TimeScaleConfig>,
+    mut time_override_events: EventWriter<SetTimeScaleOverride>,
+) {
+    rapier_config.gravity = sandbox.gravity;
+
+    *time_scale_config = TimeScaleConfig {
+        normal: sandbox.initial_time_scale.normal * sandbox.time_scale,
+        slowed: sandbox.initial_time_scale.slowed * sandbox.time_scale,
+    };
+    // Reapplies the current time scale with the new config.
+    time_override_events.write(SetTimeScaleOverride(None));
+}
+
+fn ctrl_pressed(keyboard_input: &ButtonInput<KeyCode>) -> bool {
+    keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight])
+}
+
+/// Clicks on the panel and clicks spawning particles shouldn't launch the player.
+fn ignore_drags_over_panel(
+    mut contexts: EguiContexts,
+    input: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut input_controller: ResMut<DragInputController>,
+) {
+    if input.just_pressed(key_bindings.launch)
+        && (ctrl_pressed(&keyboard_input) || contexts.ctx_mut().is_pointer_over_area())
+    {
+        input_controller.initial_position = None;
+        input_controller.vector = None;
+    }
+}
+
+fn spawn_sandbox_particle(
+    sandbox: Res<Sandbox>,
+    mut contexts: EguiContexts,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameplayCamera>>,
+    letterboxing: Res<Letterboxing>,
+    level: Single<Entity, With<RawLevel>>,
+    mut commands: Commands,
+) {
+    if !mouse_buttons.just_pressed(key_bindings.launch)
+        || !ctrl_pressed(&keyboard_input)
+        || contexts.ctx_mut().is_pointer_over_area()
+    {
+        return;
+    }
+
+    let Some(position) = mouse_world_position(&window_query, &camera_query, &letterboxing) else {
+        return;
+    };
+
+    commands.trigger(SpawnParticle {
+        translation: position,
+        particle: sandbox.particle.clone(),
+        spawn_with_invincible: false,
+        show_arrows: true,
+        parent: Some(*level),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{demo::difficulty::SlowMotion, settings::Settings};
+
+    #[test]
+    fn sandbox_physics_scale_the_initial_time_scale() {
+        let mut world = World::new();
+        world.init_resource::<Events<SetTimeScaleOverride>>();
+        world.init_resource::<TimeScaleConfig>();
+        world.spawn(RapierConfiguration {
+            gravity: Vec2::ZERO,
+            physics_pipeline_active: true,
+            query_pipeline_active: true,
+            scaled_shape_subdivision: 10,
+            force_update_from_transform_changes: false,
+        });
+
+        let initial_time_scale = TimeScaleConfig::default();
+        world.insert_resource(Sandbox {
+            particle: Particle::default(),
+            gravity: vec2(0.0, -500.0),
+            time_scale: 2.0,
+            initial_gravity: Vec2::ZERO,
+            initial_time_scale: initial_time_scale.clone(),
+        });
+        world.run_system_once(apply_sandbox_physics).unwrap();
+
+        let gravity = world
+            .query::<&RapierConfiguration>()
+            .single(&world)
+            .unwrap()
+            .gravity;
+        assert_eq!(gravity, vec2(0.0, -500.0));
+        let config = world.resource::<TimeScaleConfig>();
+        assert_eq!(config.normal, 2.0 * initial_time_scale.normal);
+        assert_eq!(config.slowed, 2.0 * initial_time_scale.slowed);
+
+        // Changed from the pause menu while in the sandbox.
+        world.insert_resource(Settings {
+            slow_motion: SlowMotion(0.2),
+            ..default()
+        });
+        let mut schedule = Schedule::default();
+        schedule.add_systems((end_sandbox, apply_slow_motion).chain());
+        schedule.run(&mut world);
+
+        let config = world.resource::<TimeScaleConfig>();
+        assert_eq!(config.normal, initial_time_scale.normal);
+        assert_eq!(config.slowed, 0.2);
+        assert!(!world.contains_resource::<Sandbox>());
+    }
+}
@@ -1,24 +1,67 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
 use bevy_rapier2d::plugin::TimestepMode;
 
-use crate::{AppSystems, screens::Screen};
+use crate::{AppSystems, PausableSystems, screens::Screen, theme::widget};
 
-pub(super) fn plugin(app: &mut App) {
-    app.register_type::<TimeScale>();
-    app.register_type::<TimeScaleOverride>();
+/// Slows down the physics simulation while the player is aiming.
+///
+/// Can be added by other crates with a custom [`TimeScaleConfig`].
+#[derive(Default)]
+pub struct TimeScalePlugin {
+    pub config: TimeScaleConfig,
+}
+
+impl Plugin for TimeScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TimeScaleConfig>();
+        app.register_type::<TimeScale>();
+        app.register_type::<TimeScaleOverride>();
 
-    app.init_resource::<TimeScale>();
-    app.init_resource::<TimeScaleOverride>();
+        app.insert_resource(self.config.clone());
+        app.init_resource::<TimeScale>();
+        app.init_resource::<TimeScaleOverride>();
+        app.init_resource::<Time<Game>>();
 
-    app.add_event::<SetTimeScale>();
-    app.add_event::<SetTimeScaleOverride>();
+        app.add_event::<SetTimeScale>();
+        app.add_event::<SetTimeScaleOverride>();
+
+        app.add_systems(OnEnter(Screen::Gameplay), spawn_override_indicator);
+        app.add_systems(
+            Update,
+            (
+                set_time_scale,
+                set_time_scale_override,
+                apply_time_scale_config.run_if(resource_changed::<TimeScaleConfig>),
+                update_override_indicator,
+            )
+                .chain()
+                .in_set(AppSystems::Update)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+        app.add_systems(
+            Update,
+            update_game_time
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+        );
+    }
+}
+
+/// The physics time scale of each [`TimeScaleKind`].
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct TimeScaleConfig {
+    pub normal: f32,
+    pub slowed: f32,
+}
 
-    app.add_systems(
-        Update,
-        (set_time_scale, set_time_scale_override)
-            .in_set(AppSystems::Update)
-            .run_if(in_state(Screen::Gameplay)),
-    );
+impl Default for TimeScaleConfig {
+    fn default() -> Self {
+        Self {
+            normal: 1.0,
+            slowed: 0.1,
+        }
+    }
 }
 
 #[derive(Default, Clone, Copy, Reflect)]
@@ -29,10 +72,10 @@ pub enum TimeScaleKind {
 }
 
 impl TimeScaleKind {
-    fn value(&self) -> f32 {
+    fn value(&self, config: &TimeScaleConfig) -> f32 {
         match *self {
-            TimeScaleKind::Normal => 1.0,
-            TimeScaleKind::Slowed => 0.1,
+            TimeScaleKind::Normal => config.normal,
+            TimeScaleKind::Slowed => config.slowed,
         }
     }
 }
@@ -41,6 +84,30 @@ impl TimeScaleKind {
 #[reflect(Resource)]
 struct TimeScale(TimeScaleKind);
 
+/// Gameplay time, which runs at the speed of the physics simulation and stops while paused.
+///
+/// Gameplay timers opt into it by ticking with `Res<Time<Game>>` instead of `Res<Time>`.
+#[derive(Default, Clone, Copy)]
+pub struct Game;
+
+/// Advances [`Time<Game>`] by the frame time scaled with the physics time scale.
+pub fn update_game_time(
+    time: Res<Time>,
+    timestep_mode: Res<TimestepMode>,
+    mut game_time: ResMut<Time<Game>>,
+) {
+    game_time.advance_by(time.delta().mul_f32(physics_time_scale(&timestep_mode)));
+}
+
+/// Returns the time scale the physics simulation currently runs with.
+pub fn physics_time_scale(timestep_mode: &TimestepMode) -> f32 {
+    match *timestep_mode {
+        TimestepMode::Variable { time_scale, .. }
+        | TimestepMode::Interpolated { time_scale, .. } => time_scale,
+        TimestepMode::Fixed { .. } => 1.0,
+    }
+}
+
 #[derive(Event)]
 pub struct SetTimeScale(pub TimeScaleKind);
 
@@ -48,6 +115,7 @@ fn set_time_scale(
     mut events: EventReader<SetTimeScale>,
     mut time_scale_resource: ResMut<TimeScale>,
     time_scale_override: Res<TimeScaleOverride>,
+    config: Res<TimeScaleConfig>,
     mut timestep_mode: ResMut<TimestepMode>,
 ) {
     for event in events.read() {
@@ -55,16 +123,39 @@ fn set_time_scale(
 
         if time_scale_override.0.is_none() {
             if let TimestepMode::Variable { time_scale, .. } = timestep_mode.as_mut() {
-                *time_scale = time_scale_resource.0.value();
+                *time_scale = time_scale_resource.0.value(&config);
             }
         }
     }
 }
 
+/// Applies changes to the [`TimeScaleConfig`] right away, instead of on the next change of the
+/// time scale.
+fn apply_time_scale_config(
+    time_scale_resource: Res<TimeScale>,
+    time_scale_override: Res<TimeScaleOverride>,
+    config: Res<TimeScaleConfig>,
+    mut timestep_mode: ResMut<TimestepMode>,
+) {
+    let kind = time_scale_override.0.unwrap_or(time_scale_resource.0);
+    if let TimestepMode::Variable { time_scale, .. } = timestep_mode.as_mut() {
+        *time_scale = kind.value(&config);
+    }
+}
+
 #[derive(Resource, Default, Reflect)]
 #[reflect(Resource)]
 struct TimeScaleOverride(Option<TimeScaleKind>);
 
+/// Back to the normal time scale without an override, for when the player stops playing.
+pub fn reset_time_scale(
+    time_events: &mut EventWriter<SetTimeScale>,
+    time_override_events: &mut EventWriter<SetTimeScaleOverride>,
+) {
+    time_override_events.write(SetTimeScaleOverride(None));
+    time_events.write(SetTimeScale(TimeScaleKind::Normal));
+}
+
 #[derive(Event)]
 pub struct SetTimeScaleOverride(pub Option<TimeScaleKind>);
 
@@ -72,6 +163,7 @@ fn set_time_scale_override(
     mut events: EventReader<SetTimeScaleOverride>,
     time_scale_resource: Res<TimeScale>,
     mut time_scale_override: ResMut<TimeScaleOverride>,
+    config: Res<TimeScaleConfig>,
     mut timestep_mode: ResMut<TimestepMode>,
 ) {
     for event in events.read() {
@@ -79,10 +171,117 @@ fn set_time_scale_override(
 
         if let TimestepMode::Variable { time_scale, .. } = timestep_mode.as_mut() {
             if let Some(ov) = time_scale_override.0 {
-                *time_scale = ov.value();
+                *time_scale = ov.value(&config);
             } else {
-                *time_scale = time_scale_resource.0.value();
+                *time_scale = time_scale_resource.0.value(&config);
             }
         }
     }
 }
+
+/// Shown while the time scale is overridden, so the player knows why the game isn't slowed.
+#[derive(Component)]
+struct OverrideIndicator;
+
+/// In the bottom right corner, the top of the screen is taken by the HUD and the time limit.
+fn spawn_override_indicator(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Time Scale Override"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Px(0.0),
+            right: Px(0.0),
+            padding: UiRect::all(Px(20.0)),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(
+            widget::label("Normal Speed"),
+            OverrideIndicator,
+            Visibility::Hidden
+        )],
+    ));
+}
+
+fn update_override_indicator(
+    time_scale_override: Res<TimeScaleOverride>,
+    mut indicator: Single<&mut Visibility, With<OverrideIndicator>>,
+) {
+    **indicator = if time_scale_override.0.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    fn test_app(time_scale: f32) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<Time<Game>>();
+        app.add_systems(Update, update_game_time);
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            0.1,
+        )));
+        app.insert_resource(TimestepMode::Variable {
+            max_dt: 1.0 / 60.0,
+            time_scale,
+            substeps: 1,
+        });
+
+        app
+    }
+
+    fn elapsed_secs(app: &mut App) -> (f32, f32) {
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let world = app.world();
+        (
+            world.resource::<Time>().elapsed_secs(),
+            world.resource::<Time<Game>>().elapsed_secs(),
+        )
+    }
+
+    #[test]
+    fn game_time_matches_real_time_at_normal_speed() {
+        let mut app = test_app(1.0);
+
+        let (real, game) = elapsed_secs(&mut app);
+        assert!((real - game).abs() < 1e-4);
+    }
+
+    #[test]
+    fn the_indicator_follows_the_override() {
+        let mut world = World::new();
+        world.init_resource::<TimeScaleOverride>();
+        let indicator = world.spawn((OverrideIndicator, Visibility::Hidden)).id();
+
+        world.resource_mut::<TimeScaleOverride>().0 = Some(TimeScaleKind::Normal);
+        world.run_system_once(update_override_indicator).unwrap();
+        assert_eq!(world.get(indicator), Some(&Visibility::Inherited));
+
+        world.resource_mut::<TimeScaleOverride>().0 = None;
+        world.run_system_once(update_override_indicator).unwrap();
+        assert_eq!(world.get(indicator), Some(&Visibility::Hidden));
+    }
+
+    #[test]
+    fn game_time_is_scaled_when_slowed() {
+        let mut app = test_app(0.1);
+
+        let (real, game) = elapsed_secs(&mut app);
+        assert!(0.0 < real);
+        assert!((real * 0.1 - game).abs() < 1e-4);
+    }
+}
@@ -16,7 +16,9 @@ use bevy::ecs::{
 ///
 /// # Example
 ///
-/// ```rust
+/// Written against the original crate, so it isn't run as a doctest here.
+///
+/// ```rust,ignore
 /// use bevy_ecs::prelude::*;
 /// use bevy_ecs::system::RunSystemOnce;
 /// use i_cant_believe_its_not_bsn::Maybe;
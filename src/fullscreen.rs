@@ -0,0 +1,45 @@
+//! Toggle fullscreen with a hotkey, remembered in the [`Settings`].
+//!
+//! The gameplay is letterboxed to any window size, so nothing else has to react to the change.
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+
+use crate::{key_bindings::KeyBindings, settings::Settings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            toggle_fullscreen,
+            apply_fullscreen.run_if(resource_changed::<Settings>),
+        )
+            .chain(),
+    );
+}
+
+fn toggle_fullscreen(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut settings: ResMut<Settings>,
+) {
+    if keyboard_input.just_pressed(key_bindings.fullscreen) {
+        settings.fullscreen = !settings.fullscreen;
+    }
+}
+
+/// On the web the browser's fullscreen API is used, which may refuse if the page isn't focused.
+fn apply_fullscreen(settings: Res<Settings>, mut window: Single<&mut Window, With<PrimaryWindow>>) {
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+
+    // Avoid touching the window when only other settings changed.
+    if window.mode != mode {
+        window.mode = mode;
+    }
+}
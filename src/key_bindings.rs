@@ -0,0 +1,57 @@
+//! The keys and mouse buttons used for gameplay.
+//!
+//! Read them from the [`KeyBindings`] resource instead of hardcoding them,
+//! so menus describing the controls stay accurate.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<KeyBindings>();
+    app.init_resource::<KeyBindings>();
+}
+
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct KeyBindings {
+    /// Held while dragging away from the player, released to launch.
+    pub launch: MouseButton,
+    /// Held to play at normal speed instead of slow motion.
+    pub normal_speed: MouseButton,
+    pub restart: KeyCode,
+    pub pause: KeyCode,
+    pub fullscreen: KeyCode,
+    /// Only available on native builds.
+    pub screenshot: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            launch: MouseButton::Left,
+            normal_speed: MouseButton::Right,
+            restart: KeyCode::Space,
+            pause: KeyCode::KeyP,
+            fullscreen: KeyCode::F11,
+            screenshot: KeyCode::F12,
+        }
+    }
+}
+
+/// A short, human readable name of a key, e.g. `P` for [`KeyCode::KeyP`].
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+
+    ["Key", "Digit"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map(str::to_string)
+        .unwrap_or(name)
+}
+
+/// A human readable name of a mouse button, e.g. `Left Click` for [`MouseButton::Left`].
+pub fn mouse_button_name(button: MouseButton) -> String {
+    match button {
+        MouseButton::Other(id) => format!("Mouse Button {id}"),
+        button => format!("{button:?} Click"),
+    }
+}
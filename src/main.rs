@@ -3,161 +3,53 @@
 // Disable console on Windows for non-dev builds.
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
-mod asset_tracking;
-mod audio;
-mod camera;
-mod demo;
-#[cfg(feature = "dev")]
-mod dev_tools;
-mod external;
-mod menus;
-mod physics;
-mod screens;
-mod theme;
-
-use bevy::{asset::AssetMetaCheck, prelude::*};
-use bevy_enoki::EnokiPlugin;
-use bevy_inspector_egui::bevy_egui::EguiPlugin;
-use bevy_rapier2d::{prelude::*, rapier::prelude::IntegrationParameters};
-
-use crate::demo::particle_effect::ParticleEffectPlugin;
+use bevy::prelude::*;
+use bevy_jam_6::AppPlugin;
+#[cfg(not(target_family = "wasm"))]
+use bevy_jam_6::screens::LaunchTarget;
 
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
-}
-
-pub struct AppPlugin;
-
-impl Plugin for AppPlugin {
-    fn build(&self, app: &mut App) {
-        // Add core plugins.
-        app.add_plugins((
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
-                    ..default()
-                })
-                .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "Bevy Jam 6".to_string(),
-                        fit_canvas_to_parent: true,
-                        ..default()
-                    }
-                    .into(),
-                    ..default()
-                }),
-            RapierPhysicsPlugin::<NoUserData>::default()
-                .with_custom_initialization(
-                    RapierContextInitialization::InitializeDefaultRapierContext {
-                        integration_parameters: IntegrationParameters::default(),
-                        rapier_configuration: RapierConfiguration {
-                            gravity: Vec2::ZERO,
-                            physics_pipeline_active: true,
-                            query_pipeline_active: true,
-                            scaled_shape_subdivision: 10,
-                            force_update_from_transform_changes: false,
-                        },
-                    },
-                )
-                .with_default_system_setup(false),
-        ));
-
-        app.add_plugins(EnokiPlugin);
-
-        app.add_plugins(EguiPlugin {
-            enable_multipass_for_primary_context: true,
-        });
-
-        app.add_plugins(MeshPickingPlugin);
-
-        // app.add_plugins(HanabiPlugin);
-        // Add other plugins.
-        app.add_plugins((
-            physics::plugin,
-            camera::plugin,
-            asset_tracking::plugin,
-            audio::plugin,
-            demo::plugin,
-            // #[cfg(feature = "dev")]
-            // dev_tools::plugin,
-            menus::plugin,
-            screens::plugin,
-            theme::plugin,
-            ParticleEffectPlugin,
-        ));
-
-        // Order new `AppSystems` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (
-                AppSystems::TickTimers,
-                AppSystems::RecordInput,
-                AppSystems::Update,
-            )
-                .chain(),
-        );
-
-        // Set up the `Pause` state.
-        app.init_state::<Pause>();
-        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
-        app.configure_sets(PostUpdate, PausableSystems.run_if(in_state(Pause(false))));
-
-        // Configure Rapier.
-        app.configure_sets(
-            PostUpdate,
-            (
-                PhysicsSet::SyncBackend,
-                PhysicsSet::StepSimulation,
-                PhysicsSet::Writeback,
-            )
-                .chain()
-                .before(TransformSystem::TransformPropagate),
-        );
-
-        app.add_systems(
-            PostUpdate,
-            (
-                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::SyncBackend)
-                    .in_set(PhysicsSet::SyncBackend)
-                    .in_set(PausableSystems),
-                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::StepSimulation)
-                    .in_set(PhysicsSet::StepSimulation)
-                    .in_set(PausableSystems),
-                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::Writeback)
-                    .in_set(PhysicsSet::Writeback)
-                    .in_set(PausableSystems),
-            ),
-        );
-
-        app.insert_resource(TimestepMode::Variable {
-            max_dt: 1.0 / 60.0,
-            time_scale: 1.0,
-            substeps: 2,
-        });
+    let mut app = App::new();
+    app.add_plugins(AppPlugin::default());
+
+    #[cfg(not(target_family = "wasm"))]
+    match parse_launch_target() {
+        Ok(Some(launch_target)) => {
+            app.insert_resource(launch_target);
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("{err}");
+            return AppExit::error();
+        }
     }
-}
 
-/// High-level groupings of systems for the app in the `Update` schedule.
-/// When adding a new variant, make sure to order it in the `configure_sets`
-/// call above.
-#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
-enum AppSystems {
-    /// Tick timers.
-    TickTimers,
-    /// Record player input.
-    RecordInput,
-    /// Do everything else (consider splitting this into further variants).
-    Update,
+    app.run()
 }
 
-/// Whether or not the game is paused.
-#[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
-#[states(scoped_entities)]
-struct Pause(pub bool);
+/// Parses the `--level <number>` and `--editor` command-line arguments.
+#[cfg(not(target_family = "wasm"))]
+fn parse_launch_target() -> Result<Option<LaunchTarget>, String> {
+    let mut args = std::env::args().skip(1);
+    let mut launch_target = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--level" => {
+                let value = args.next().ok_or("`--level` expects a level number")?;
+                let number = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&number| number > 0)
+                    .ok_or(format!(
+                        "`--level` expects a level number starting from 1, got `{value}`"
+                    ))?;
+                launch_target = Some(LaunchTarget::Level(number - 1));
+            }
+            "--editor" => launch_target = Some(LaunchTarget::Editor),
+            _ => {}
+        }
+    }
 
-/// A system set for systems that shouldn't run while the game is paused.
-#[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
-struct PausableSystems;
+    Ok(launch_target)
+}
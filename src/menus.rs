@@ -2,6 +2,7 @@
 
 mod credits;
 mod end;
+mod help;
 mod levels;
 mod main;
 mod pause;
@@ -15,6 +16,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         credits::plugin,
         end::plugin,
+        help::plugin,
         levels::plugin,
         main::plugin,
         settings::plugin,
@@ -31,6 +33,7 @@ pub enum Menu {
     Levels,
     Credits,
     Settings,
+    Help,
     Pause,
     End,
 }
@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
+    demo::level::run::RestartRun,
     menus::Menu,
     screens::Screen,
-    theme::{BoldFont, palette::HEADER_TEXT, widget},
+    theme::{BoldFont, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -21,18 +22,23 @@ fn spawn_end_menu(mut commands: Commands) {
                 Text("You Win!".into()),
                 TextFont::from_font_size(80.0),
                 BoldFont,
-                TextColor(HEADER_TEXT),
+                Themed::HeaderText,
             ),
             Node {
                 height: Val::Px(20.0),
                 ..default()
             },
+            widget::button("Restart Run", restart_run),
             widget::button("Levels", quit_to_levels),
             widget::button("Quit to title", quit_to_title),
         ],
     ));
 }
 
+fn restart_run(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(RestartRun);
+}
+
 fn quit_to_levels(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Levels);
 }
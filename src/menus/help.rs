@@ -0,0 +1,140 @@
+//! The help menu, explaining the goal and the controls.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    key_bindings::{KeyBindings, key_name, mouse_button_name},
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Help), spawn_help_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Help).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_help_menu(mut commands: Commands, key_bindings: Res<KeyBindings>, theme: Res<Theme>) {
+    commands.spawn((
+        widget::ui_root("Help Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Help),
+        children![
+            widget::header("How to Play"),
+            launch_diagram(&theme),
+            widget::label("Split every particle to complete the level."),
+            controls_grid(&key_bindings),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// A static picture of the player being pulled back for a launch.
+fn launch_diagram(theme: &Theme) -> impl Bundle {
+    (
+        Name::new("Launch Diagram"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Name::new("Player"),
+                Node {
+                    width: Px(40.0),
+                    height: Px(40.0),
+                    ..default()
+                },
+                BorderRadius::MAX,
+                BackgroundColor(Color::hsl(0.0, 0.95, 0.7)),
+            ),
+            (
+                Name::new("Drag Indicator"),
+                Node {
+                    width: Px(150.0),
+                    height: Px(6.0),
+                    ..default()
+                },
+                BackgroundColor(theme.label_text),
+            ),
+            widget::label("Pull back and release"),
+        ],
+    )
+}
+
+fn controls_grid(key_bindings: &KeyBindings) -> impl Bundle {
+    #[cfg_attr(target_family = "wasm", allow(unused_mut))]
+    let mut controls = vec![
+        [
+            format!("Drag with {}", mouse_button_name(key_bindings.launch)),
+            String::from("Launch"),
+        ],
+        [
+            format!("Hold {}", mouse_button_name(key_bindings.normal_speed)),
+            String::from("Skip slow motion"),
+        ],
+        [
+            key_name(key_bindings.restart),
+            String::from("Restart level"),
+        ],
+        [key_name(key_bindings.pause), String::from("Pause")],
+        [
+            key_name(key_bindings.fullscreen),
+            String::from("Toggle fullscreen"),
+        ],
+    ];
+    #[cfg(not(target_family = "wasm"))]
+    controls.push([
+        key_name(key_bindings.screenshot),
+        String::from("Save screenshot"),
+    ]);
+
+    (
+        Name::new("Controls Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(controls.into_iter().flatten().enumerate().map(
+            |(i, text)| {
+                (
+                    widget::label(text),
+                    Node {
+                        justify_self: if i % 2 == 0 {
+                            JustifySelf::End
+                        } else {
+                            JustifySelf::Start
+                        },
+                        ..default()
+                    },
+                )
+            },
+        ))),
+    )
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    go_back(screen, next_menu);
+}
+
+/// Back to the menu the help was opened from.
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
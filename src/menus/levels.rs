@@ -6,10 +6,14 @@ use bevy::{
 };
 
 use crate::{
-    demo::level::{Level, level_loading::LevelAssets},
+    demo::level::{
+        Level,
+        level_data::LevelData,
+        level_loading::{LevelAssets, LevelInfo},
+    },
     menus::Menu,
     screens::{Screen, gameplay::SelectedLevel},
-    theme::{RegularFont, prelude::InteractionPalette, widget},
+    theme::{RegularFont, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -28,11 +32,6 @@ where
     let text = text.into();
     let action = IntoObserverSystem::into_system(action);
 
-    let none = Color::Srgba(Srgba::hex("#0f0f0f").unwrap());
-    let hovered = Color::Srgba(Srgba::hex("#000000").unwrap());
-    let pressed = Color::Srgba(Srgba::hex("#000000").unwrap());
-    let text_color = Color::Srgba(Srgba::hex("#ffffff").unwrap());
-
     (
         Name::new("Level Button"),
         Node::default(),
@@ -51,18 +50,14 @@ where
                     BorderRadius::all(Val::Px(10.0)),
                     Name::new("Button Inner"),
                     Button,
-                    BackgroundColor(none),
-                    InteractionPalette {
-                        none,
-                        hovered,
-                        pressed,
-                    },
+                    InteractionPalette::default(),
+                    Themed::LevelButton,
                     children![(
                         Name::new("Button Text"),
                         Text(text),
                         TextFont::from_font_size(40.0),
                         RegularFont,
-                        TextColor(text_color),
+                        Themed::LevelButtonText,
                         Pickable::IGNORE,
                     )],
                 ))
@@ -71,8 +66,17 @@ where
     )
 }
 
-fn spawn_levels_menu(mut commands: Commands, level_assets: Res<LevelAssets>) {
-    let num_default_levels = level_assets.default.len();
+fn spawn_levels_menu(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    levels: Res<Assets<LevelData>>,
+) {
+    // Custom levels don't have a section yet.
+    let default_levels: Vec<LevelInfo> = level_assets
+        .all_levels(&levels)
+        .into_iter()
+        .filter(|info| info.loaded && matches!(info.level, Level::Default(_)))
+        .collect();
 
     commands.spawn((
         widget::ui_root("Levels Menu"),
@@ -93,10 +97,14 @@ fn spawn_levels_menu(mut commands: Commands, level_assets: Res<LevelAssets>) {
                 },
                 Pickable::IGNORE,
                 Children::spawn(SpawnWith(move |parent: &mut RelatedSpawner<ChildOf>| {
-                    for i in 0..num_default_levels {
+                    // Numbered by id, so a level that failed to load leaves a gap.
+                    for info in default_levels {
+                        let Level::Default(id) = info.level else {
+                            continue;
+                        };
                         parent.spawn(level_button(
-                            (i + 1).to_string(),
-                            Level::Default(i),
+                            (id + 1).to_string(),
+                            info.level,
                             enter_gameplay_screen,
                         ));
                     }
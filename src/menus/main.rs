@@ -3,9 +3,10 @@
 use bevy::prelude::*;
 
 use crate::{
+    demo::sandbox::StartSandbox,
     menus::Menu,
     screens::Screen,
-    theme::{BoldFont, palette::HEADER_TEXT, widget},
+    theme::{BoldFont, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -24,7 +25,7 @@ fn spawn_main_menu(mut commands: Commands) {
                 Text("Antim4tter".into()),
                 TextFont::from_font_size(80.0),
                 BoldFont,
-                TextColor(HEADER_TEXT),
+                Themed::HeaderText,
             ),
             Node {
                 height: Val::Px(20.0),
@@ -32,6 +33,8 @@ fn spawn_main_menu(mut commands: Commands) {
             },
             widget::button("Play", enter_levels_screen),
             widget::button("Editor", enter_editor_screen),
+            widget::button("Sandbox", start_sandbox),
+            widget::button("How to Play", open_help_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
@@ -43,7 +46,7 @@ fn spawn_main_menu(mut commands: Commands) {
                 Text("Antim4tter".into()),
                 TextFont::from_font_size(80.0),
                 BoldFont,
-                TextColor(HEADER_TEXT),
+                Themed::HeaderText,
             ),
             Node {
                 height: Val::Px(20.0),
@@ -51,6 +54,8 @@ fn spawn_main_menu(mut commands: Commands) {
             },
             widget::button("Play", enter_levels_screen),
             widget::button("Editor", enter_editor_screen),
+            widget::button("Sandbox", start_sandbox),
+            widget::button("How to Play", open_help_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
         ],
@@ -65,10 +70,18 @@ fn enter_editor_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextS
     next_screen.set(Screen::Editor);
 }
 
+fn start_sandbox(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(StartSandbox);
+}
+
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
+fn open_help_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Help);
+}
+
 fn open_credits_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Credits);
 }
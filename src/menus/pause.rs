@@ -2,13 +2,16 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{demo::editor::EditorState, menus::Menu, screens::Screen, theme::widget};
+use crate::{demo::level::PlaySource, menus::Menu, screens::Screen, theme::widget};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         OnEnter(Menu::Pause),
-        |commands: Commands, editor_state: Res<EditorState>| {
-            if editor_state.editing {
+        |commands: Commands, source_query: Query<&PlaySource>| {
+            if source_query
+                .single()
+                .is_ok_and(|source| *source == PlaySource::EditorTest)
+            {
                 spawn_editing_pause_menu(commands);
             } else {
                 spawn_pause_menu(commands);
@@ -29,6 +32,7 @@ fn spawn_editing_pause_menu(mut commands: Commands) {
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("How to Play", open_help_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Back to editor", back_to_editor),
         ],
@@ -43,6 +47,7 @@ fn spawn_pause_menu(mut commands: Commands) {
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("How to Play", open_help_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Levels", quit_to_levels),
             widget::button("Quit to title", quit_to_title),
@@ -50,6 +55,10 @@ fn spawn_pause_menu(mut commands: Commands) {
     ));
 }
 
+fn open_help_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Help);
+}
+
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
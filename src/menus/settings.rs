@@ -2,9 +2,12 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume, ecs::spawn::SpawnableList, input::common_conditions::input_just_pressed,
+    prelude::*, ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{menus::Menu, screens::Screen, settings::Settings, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -18,6 +21,23 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    app.add_systems(
+        Update,
+        update_bool_setting_labels.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<DifficultyLabel>();
+    app.add_systems(
+        Update,
+        update_difficulty_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<SlowMotionLabel>();
+    app.add_systems(
+        Update,
+        update_slow_motion_label.run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -43,16 +63,46 @@ fn settings_grid() -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        children![
-            (
-                widget::label("Master Volume"),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
+        // More rows than `children!` takes.
+        Children::spawn((
+            settings_row("Master Volume", global_volume_widget()),
+            settings_row(
+                "Pause Audio",
+                bool_setting_widget("Pause Audio", |settings| &mut settings.pause_audio),
             ),
-            global_volume_widget(),
-        ],
+            settings_row(
+                "Colorblind Mode",
+                bool_setting_widget("Colorblind Mode", |settings| &mut settings.colorblind_mode),
+            ),
+            settings_row(
+                "Ghost Trails",
+                bool_setting_widget("Ghost Trails", |settings| &mut settings.ghost_trails),
+            ),
+            settings_row(
+                "Reduce Motion",
+                bool_setting_widget("Reduce Motion", |settings| &mut settings.reduce_motion),
+            ),
+            settings_row(
+                "Retro Mode",
+                bool_setting_widget("Retro Mode", |settings| &mut settings.retro_mode),
+            ),
+            settings_row("Difficulty", difficulty_widget()),
+            settings_row("Slow Motion", slow_motion_widget()),
+        )),
+    )
+}
+
+/// A label in the first column of the grid and its widget in the second.
+fn settings_row(label: &'static str, widget: impl Bundle) -> impl SpawnableList<ChildOf> {
+    (
+        Spawn((
+            widget::label(label),
+            Node {
+                justify_self: JustifySelf::End,
+                ..default()
+            },
+        )),
+        Spawn(widget),
     )
 }
 
@@ -104,6 +154,144 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+/// A setting that is either on or off, `field` picks it out of the [`Settings`].
+fn bool_setting_widget(label: &'static str, field: fn(&mut Settings) -> &mut bool) -> impl Bundle {
+    (
+        Name::new(format!("{label} Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", set_bool_setting(field, false)),
+            (
+                Name::new(format!("Current {label}")),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), BoolSettingLabel(field))],
+            ),
+            widget::button_small(">", set_bool_setting(field, true)),
+        ],
+    )
+}
+
+/// Turns the setting off with the left button and on with the right one.
+fn set_bool_setting(
+    field: fn(&mut Settings) -> &mut bool,
+    on: bool,
+) -> impl FnMut(Trigger<Pointer<Click>>, ResMut<Settings>) {
+    move |_, mut settings| {
+        // Settings are saved when changed, so only change them if the value does.
+        if *field(settings.bypass_change_detection()) != on {
+            *field(&mut settings) = on;
+        }
+    }
+}
+
+/// Shows whether the setting of a [`bool_setting_widget`] is on.
+#[derive(Component)]
+struct BoolSettingLabel(fn(&mut Settings) -> &mut bool);
+
+fn update_bool_setting_labels(
+    mut settings: ResMut<Settings>,
+    mut label_query: Query<(&mut Text, &BoolSettingLabel)>,
+) {
+    // Only reads the settings, so they aren't saved every frame.
+    let settings = settings.bypass_change_detection();
+    for (mut text, label) in &mut label_query {
+        text.0 = String::from(if *(label.0)(settings) { "On" } else { "Off" });
+    }
+}
+
+fn difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Difficulty Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", lower_difficulty),
+            (
+                Name::new("Current Difficulty"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), DifficultyLabel)],
+            ),
+            widget::button_small(">", raise_difficulty),
+        ],
+    )
+}
+
+fn lower_difficulty(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.difficulty = settings.difficulty.step(-1);
+}
+
+fn raise_difficulty(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.difficulty = settings.difficulty.step(1);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DifficultyLabel;
+
+fn update_difficulty_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<DifficultyLabel>>,
+) {
+    label.0 = String::from(settings.difficulty.name());
+}
+
+fn slow_motion_widget() -> impl Bundle {
+    (
+        Name::new("Slow Motion Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_slow_motion),
+            (
+                Name::new("Current Slow Motion"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), SlowMotionLabel)],
+            ),
+            widget::button_small("+", raise_slow_motion),
+        ],
+    )
+}
+
+fn lower_slow_motion(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.slow_motion = settings.slow_motion.step(-1);
+}
+
+fn raise_slow_motion(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.slow_motion = settings.slow_motion.step(1);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SlowMotionLabel;
+
+/// Shown as the speed of the game while slowed.
+fn update_slow_motion_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<SlowMotionLabel>>,
+) {
+    let percent = 100.0 * settings.slow_motion.0;
+    label.0 = format!("{percent:3.0}%");
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
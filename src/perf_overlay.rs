@@ -0,0 +1,84 @@
+//! An on-screen performance readout. This plugin is only enabled with the `perf_overlay` feature.
+//!
+//! Shows FPS, the number of live particles and the total number of entities.
+//! Press [`TOGGLE_KEY`] to show or hide it.
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{demo::particle::Particle, theme::RegularFont};
+
+pub(super) fn plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+    if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+        app.add_plugins(EntityCountDiagnosticsPlugin);
+    }
+
+    app.add_systems(Startup, spawn_perf_overlay);
+    app.add_systems(
+        Update,
+        (
+            toggle_perf_overlay.run_if(input_just_pressed(TOGGLE_KEY)),
+            update_perf_overlay,
+        ),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+
+#[derive(Component)]
+struct PerfOverlay;
+
+fn spawn_perf_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Perf Overlay"),
+        PerfOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(5.0),
+            right: Px(5.0),
+            padding: UiRect::all(Px(5.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        Text::default(),
+        TextFont::from_font_size(16.0),
+        RegularFont,
+    ));
+}
+
+fn toggle_perf_overlay(mut overlay: Single<&mut Visibility, With<PerfOverlay>>) {
+    overlay.toggle_visible_hidden();
+}
+
+fn update_perf_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    particle_query: Query<(), With<Particle>>,
+    overlay: Single<(&mut Text, &Visibility), With<PerfOverlay>>,
+) {
+    let (mut text, visibility) = overlay.into_inner();
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let entities = diagnostics
+        .get(&EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+        .and_then(|count| count.value())
+        .unwrap_or_default();
+    let particles = particle_query.iter().count();
+
+    text.0 = format!("FPS: {fps:.0}\nParticles: {particles}\nEntities: {entities:.0}");
+}
@@ -14,7 +14,7 @@ pub struct CollisionHandlerSystems;
 /// Traverses the hierarchy from the given entity until the first ancestor with a rigid body.
 pub fn find_rigidbody_ancestor(
     mut entity: Entity,
-    query: &Query<(Option<&RigidBody>, &ChildOf)>,
+    query: &Query<(Option<&RigidBody>, Option<&ChildOf>)>,
 ) -> Option<Entity> {
     loop {
         let Ok((rigid_body, parent)) = query.get(entity) else {
@@ -25,6 +25,6 @@ pub fn find_rigidbody_ancestor(
             return Some(entity);
         }
 
-        entity = parent.0;
+        entity = parent?.0;
     }
 }
@@ -1,5 +1,7 @@
 //! The game's main screen states and transitions between them.
 
+mod attract;
+mod editor;
 mod end;
 pub mod gameplay;
 mod levels;
@@ -13,6 +15,8 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
     app.add_plugins((
+        attract::plugin,
+        editor::plugin,
         end::plugin,
         gameplay::plugin,
         levels::plugin,
@@ -35,3 +39,14 @@ pub enum Screen {
     Gameplay,
     End,
 }
+
+/// A screen to launch into once the assets are loaded, skipping the splash and title screens.
+///
+/// Set with the `--level <number>` and `--editor` command-line arguments on native.
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchTarget {
+    /// The index of a default level.
+    Level(usize),
+    Editor,
+}
@@ -0,0 +1,298 @@
+//! An attract mode, playing a demo run of the first level after idling on the title screen.
+//!
+//! There is no replay recording yet, so the demo run is a short list of launches written by hand.
+//! Any input returns to the title screen.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, window::CursorMoved};
+
+use crate::{
+    AppSystems, PausableSystems,
+    demo::{
+        drag_input::StretchInputEvent,
+        level::{PlaySource, SpawnRawLevel, level_data::LevelData, level_loading::LevelAssets},
+    },
+    menus::Menu,
+    screens::{Screen, gameplay::SelectedLevel},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AttractConfig>();
+    app.init_resource::<AttractConfig>();
+
+    app.add_systems(
+        Update,
+        start_attract_mode.run_if(in_state(Screen::Title).and(in_state(Menu::Main))),
+    );
+    app.add_systems(
+        Update,
+        (
+            play_demo_run
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems),
+            stop_attract_mode.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<AttractMode>)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), end_attract_mode);
+}
+
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct AttractConfig {
+    pub enabled: bool,
+    /// How long the title screen has to be idle before the demo run starts.
+    pub idle_time: Duration,
+    /// How long the demo run plays before returning to the title screen.
+    pub run_time: Duration,
+}
+
+impl Default for AttractConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_time: Duration::from_secs(30),
+            run_time: Duration::from_secs(15),
+        }
+    }
+}
+
+/// A launch of the demo run, `time` after the level started.
+struct DemoLaunch {
+    time: Duration,
+    vector: Vec2,
+}
+
+/// The index of the default level the demo run plays.
+const DEMO_LEVEL: usize = 0;
+
+/// Through the row of particles of the first level and then up, relaunching after each hit so the
+/// game doesn't stay slowed down.
+const DEMO_LAUNCHES: &[DemoLaunch] = &[
+    DemoLaunch {
+        time: Duration::from_millis(1500),
+        vector: Vec2::new(250.0, 0.0),
+    },
+    DemoLaunch {
+        time: Duration::from_millis(2000),
+        vector: Vec2::new(250.0, 0.0),
+    },
+    DemoLaunch {
+        time: Duration::from_millis(2700),
+        vector: Vec2::new(250.0, 0.0),
+    },
+    DemoLaunch {
+        time: Duration::from_millis(3300),
+        vector: Vec2::new(0.0, 250.0),
+    },
+];
+
+/// The demo run being played, removed when leaving gameplay.
+#[derive(Resource, Default)]
+struct AttractMode {
+    elapsed: Duration,
+}
+
+/// The launches due after `from` and up to `to`.
+fn launches_between(
+    launches: &[DemoLaunch],
+    from: Duration,
+    to: Duration,
+) -> impl Iterator<Item = &DemoLaunch> {
+    launches
+        .iter()
+        .filter(move |launch| from < launch.time && launch.time <= to)
+}
+
+fn any_input(
+    keyboard_input: &ButtonInput<KeyCode>,
+    mouse_input: &ButtonInput<MouseButton>,
+    cursor_moved: &mut EventReader<CursorMoved>,
+) -> bool {
+    // Read every event, so old ones don't count once the reader is needed again.
+    let moved = cursor_moved.read().count() > 0;
+    moved
+        || keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+}
+
+fn start_attract_mode(
+    config: Res<AttractConfig>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    level_assets: Res<LevelAssets>,
+    levels: Res<Assets<LevelData>>,
+    time: Res<Time>,
+    mut idle: Local<Duration>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    if !config.enabled || any_input(&keyboard_input, &mouse_input, &mut cursor_moved) {
+        *idle = Duration::ZERO;
+        return;
+    }
+
+    *idle += time.delta();
+    if *idle < config.idle_time {
+        return;
+    }
+    let Some(data) = level_assets
+        .default
+        .get(DEMO_LEVEL)
+        .and_then(|handle| levels.get(handle))
+    else {
+        return;
+    };
+    *idle = Duration::ZERO;
+
+    debug!("Starting the attract mode");
+    commands.init_resource::<AttractMode>();
+    // Spawned as a demo, so it neither counts towards the player's progress nor moves on to the
+    // next level.
+    commands.trigger(SpawnRawLevel {
+        data: data.clone(),
+        level: None,
+        source: PlaySource::Demo,
+    });
+    selected_level.0 = None;
+    next_screen.set(Screen::Gameplay);
+}
+
+fn play_demo_run(
+    mut attract_mode: ResMut<AttractMode>,
+    time: Res<Time>,
+    mut events: EventWriter<StretchInputEvent>,
+) {
+    let from = attract_mode.elapsed;
+    attract_mode.elapsed += time.delta();
+
+    for launch in launches_between(DEMO_LAUNCHES, from, attract_mode.elapsed) {
+        events.write(StretchInputEvent {
+            vector: launch.vector,
+        });
+    }
+}
+
+fn stop_attract_mode(
+    config: Res<AttractConfig>,
+    attract_mode: Res<AttractMode>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if any_input(&keyboard_input, &mouse_input, &mut cursor_moved)
+        || config.run_time <= attract_mode.elapsed
+    {
+        next_screen.set(Screen::Title);
+    }
+}
+
+fn end_attract_mode(mut commands: Commands) {
+    commands.remove_resource::<AttractMode>();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+    use bevy_rapier2d::prelude::*;
+
+    use super::*;
+    use crate::{
+        audio::music::MusicAssets,
+        camera::Letterboxing,
+        demo::{
+            level::{objective::ObjectiveProgress, spawn_raw_level},
+            particle::{Particle, ParticleAssets, PooledParticle, physics_app},
+            player::{FirstLaunch, PlayerAssets, PlayerLaunched, handle_drag_input},
+            time_scale::TimeScalePlugin,
+        },
+        physics,
+        theme::palette::Theme,
+    };
+
+    /// Plays the demo run on the demo level with the game's physics, for as long as it runs.
+    #[test]
+    fn demo_run_completes_the_demo_level() {
+        let mut app = physics_app();
+        app.add_plugins((StatesPlugin, TimeScalePlugin::default(), physics::plugin));
+        app.insert_state(Screen::Gameplay);
+        app.insert_resource(TimestepMode::Variable {
+            max_dt: 1.0 / 60.0,
+            time_scale: 1.0,
+            substeps: 2,
+        });
+        app.init_resource::<ParticleAssets>();
+        app.init_resource::<PlayerAssets>();
+        app.init_resource::<Theme>();
+        app.init_resource::<Letterboxing>();
+        app.init_resource::<MusicAssets>();
+        app.add_event::<StretchInputEvent>();
+        app.add_event::<PlayerLaunched>();
+        app.add_event::<FirstLaunch>();
+        app.add_observer(spawn_raw_level);
+        app.init_resource::<AttractMode>();
+        app.add_systems(Update, (play_demo_run, handle_drag_input).chain());
+
+        // Default levels are numbered from one.
+        let path = format!(
+            "{}/assets/levels/default/{}.ron",
+            env!("CARGO_MANIFEST_DIR"),
+            DEMO_LEVEL + 1
+        );
+        let data = LevelData::from_ron(&std::fs::read(path).unwrap()).unwrap();
+        let objective = data.objective;
+        app.world_mut().trigger(SpawnRawLevel {
+            data,
+            level: None,
+            source: PlaySource::Demo,
+        });
+
+        let run_time = AttractConfig::default().run_time;
+        while app.world().resource::<AttractMode>().elapsed < run_time {
+            app.update();
+        }
+
+        let particles = app
+            .world_mut()
+            .query_filtered::<(), (With<Particle>, Without<PooledParticle>)>()
+            .iter(app.world())
+            .count();
+        assert!(objective.is_complete(&ObjectiveProgress {
+            particles,
+            killers: 0,
+            elapsed: run_time,
+            player_alive: true,
+        }));
+    }
+
+    #[test]
+    fn each_launch_is_due_once() {
+        let launches = [
+            DemoLaunch {
+                time: Duration::from_secs(1),
+                vector: Vec2::X,
+            },
+            DemoLaunch {
+                time: Duration::from_secs(2),
+                vector: Vec2::Y,
+            },
+        ];
+        let due = |from, to| {
+            launches_between(
+                &launches,
+                Duration::from_secs(from),
+                Duration::from_secs(to),
+            )
+            .map(|launch| launch.vector)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(due(0, 1), [Vec2::X]);
+        assert_eq!(due(1, 3), [Vec2::Y]);
+        assert!(due(3, 4).is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+//! The screen state for the level editor.
+
+use bevy::prelude::*;
+
+use crate::{demo::editor::SpawnEditor, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Editor), spawn_editor);
+}
+
+fn spawn_editor(mut commands: Commands) {
+    commands.trigger(SpawnEditor);
+}
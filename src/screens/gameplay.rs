@@ -4,26 +4,22 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*}
 
 use crate::{
     Pause,
-    demo::level::{Level, SpawnLevel},
+    demo::level::{Level, RawLevel, SpawnLevel},
+    key_bindings::KeyBindings,
     menus::Menu,
     screens::Screen,
 };
 
-/// The level selected to be played.
+/// The level selected to be played, it is spawned when entering [`Screen::Gameplay`].
+///
+/// Leave it empty if the level is spawned by other means, e.g. by the editor.
 #[derive(Resource, Default)]
 pub struct SelectedLevel(pub Option<Level>);
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<SelectedLevel>();
 
-    app.add_systems(
-        OnEnter(Screen::Gameplay),
-        |mut commands: Commands, selected_level: Res<SelectedLevel>| {
-            if let Some(level) = selected_level.0.clone() {
-                commands.trigger(SpawnLevel(level))
-            }
-        },
-    );
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_selected_level);
 
     // Toggle pause on key press.
     app.add_systems(
@@ -32,12 +28,12 @@ pub(super) fn plugin(app: &mut App) {
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
                 in_state(Screen::Gameplay)
                     .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                    .and(pause_just_pressed.or(input_just_pressed(KeyCode::Escape))),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(pause_just_pressed),
             ),
         ),
     );
@@ -48,6 +44,30 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// Triggers [`SpawnLevel`] for the [`SelectedLevel`].
+///
+/// Falls back to the level selection if there is neither a selected level nor an already spawned one.
+pub fn spawn_selected_level(
+    mut commands: Commands,
+    selected_level: Res<SelectedLevel>,
+    raw_level_query: Query<(), With<RawLevel>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    match selected_level.0.clone() {
+        Some(level) => commands.trigger(SpawnLevel(level)),
+        // The editor spawns the level it plays by itself.
+        None if !raw_level_query.is_empty() => {}
+        None => {
+            warn!("Entered gameplay without a selected level, returning to the level selection.");
+            next_screen.set(Screen::Levels);
+        }
+    }
+}
+
+fn pause_just_pressed(input: Res<ButtonInput<KeyCode>>, key_bindings: Res<KeyBindings>) -> bool {
+    input.just_pressed(key_bindings.pause)
+}
+
 fn unpause(mut next_pause: ResMut<NextState<Pause>>) {
     next_pause.set(Pause(false));
 }
@@ -77,3 +97,67 @@ fn open_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct SpawnedLevels(Vec<Level>);
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.init_state::<Screen>();
+        app.init_state::<Menu>();
+        app.init_state::<Pause>();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.init_resource::<KeyBindings>();
+        app.add_plugins(plugin);
+
+        app.init_resource::<SpawnedLevels>();
+        app.add_observer(
+            |trigger: Trigger<SpawnLevel>, mut spawned_levels: ResMut<SpawnedLevels>| {
+                spawned_levels.0.push(trigger.0.clone());
+            },
+        );
+
+        app
+    }
+
+    fn enter_gameplay(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+        app.update();
+    }
+
+    #[test]
+    fn entering_gameplay_spawns_selected_level() {
+        let mut app = test_app();
+
+        app.insert_resource(SelectedLevel(Some(Level::Default(2))));
+        enter_gameplay(&mut app);
+
+        assert_eq!(
+            app.world().resource::<SpawnedLevels>().0,
+            [Level::Default(2)]
+        );
+    }
+
+    #[test]
+    fn entering_gameplay_without_selected_level_returns_to_levels() {
+        let mut app = test_app();
+
+        enter_gameplay(&mut app);
+        app.update();
+
+        assert!(app.world().resource::<SpawnedLevels>().0.is_empty());
+        assert_eq!(
+            *app.world().resource::<State<Screen>>().get(),
+            Screen::Levels
+        );
+    }
+}
@@ -3,14 +3,29 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
+use crate::{
+    asset_tracking::ResourceHandles,
+    demo::level::{Level, level_loading::LevelAssets},
+    screens::{LaunchTarget, Screen, gameplay::SelectedLevel},
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
 
+    // Skip the splash screen when launching straight into a screen.
+    app.add_systems(
+        OnEnter(Screen::Splash),
+        enter_loading_screen.run_if(resource_exists::<LaunchTarget>),
+    );
+
     app.add_systems(
         Update,
-        enter_title_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
+        (
+            enter_title_screen.run_if(not(resource_exists::<LaunchTarget>)),
+            enter_launch_target.run_if(resource_exists::<LaunchTarget>),
+        )
+            .run_if(in_state(Screen::Loading).and(all_assets_loaded)),
     );
 }
 
@@ -22,10 +37,45 @@ fn spawn_loading_screen(mut commands: Commands) {
     ));
 }
 
+fn enter_loading_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
+
 fn enter_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
 
+fn enter_launch_target(
+    launch_target: Res<LaunchTarget>,
+    level_assets: Option<Res<LevelAssets>>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    // The level assets are initialized a frame after the level handles are loaded.
+    let Some(level_assets) = level_assets else {
+        return;
+    };
+
+    match *launch_target {
+        LaunchTarget::Level(id) if id < level_assets.default.len() => {
+            selected_level.0 = Some(Level::Default(id));
+            next_screen.set(Screen::Gameplay);
+        }
+        LaunchTarget::Level(id) => {
+            error!(
+                "Can't launch level {}, there are only {} levels.",
+                id + 1,
+                level_assets.default.len()
+            );
+            next_screen.set(Screen::Title);
+        }
+        LaunchTarget::Editor => next_screen.set(Screen::Editor),
+    }
+
+    commands.remove_resource::<LaunchTarget>();
+}
+
 fn all_assets_loaded(resource_handles: Res<ResourceHandles>) -> bool {
     resource_handles.is_all_done()
 }
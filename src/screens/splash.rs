@@ -10,7 +10,6 @@ use crate::{AppSystems, asset_tracking::ResourceHandles, screens::Screen, theme:
 
 pub(super) fn plugin(app: &mut App) {
     // Spawn splash screen.
-    app.insert_resource(ClearColor(SPLASH_BACKGROUND_COLOR));
     app.add_systems(OnEnter(Screen::Splash), spawn_splash_screen);
 
     // Animate splash screen.
@@ -0,0 +1,47 @@
+//! Save the gameplay render target to a PNG next to the executable.
+
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{Screenshot, save_to_disk},
+};
+
+use crate::{camera::GameplayRenderTarget, key_bindings::KeyBindings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, take_screenshot);
+}
+
+/// Where a screenshot taken now is saved, e.g. `screenshot-1718000000123.png`.
+fn screenshot_path() -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let file_name = format!("screenshot-{millis}.png");
+
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&file_name)))
+        .unwrap_or_else(|| PathBuf::from(file_name))
+}
+
+/// The image is read back from the GPU over the next frames, then saved by the observer.
+fn take_screenshot(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    target: Single<&GameplayRenderTarget>,
+    mut commands: Commands,
+) {
+    if !keyboard_input.just_pressed(key_bindings.screenshot) {
+        return;
+    }
+
+    commands
+        .spawn(Screenshot::image(target.0.clone()))
+        .observe(save_to_disk(screenshot_path()));
+}
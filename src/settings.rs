@@ -0,0 +1,102 @@
+//! Player settings that are kept between runs.
+//!
+//! On native builds the [`Settings`] resource is loaded from and saved to [`settings_path`],
+//! on the web it only lives for the session.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::demo::difficulty::{Difficulty, SlowMotion};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    app.insert_resource(Settings::load());
+
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        Update,
+        save_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
+}
+
+#[cfg(not(target_family = "wasm"))]
+const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+/// Next to the executable, so the settings don't depend on where the game was started from.
+#[cfg(not(target_family = "wasm"))]
+fn settings_path() -> std::path::PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(SETTINGS_FILE_NAME)))
+        .unwrap_or_else(|| SETTINGS_FILE_NAME.into())
+}
+
+#[derive(Resource, Reflect, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// Uses colors that are easier to tell apart and outlines killers.
+    pub colorblind_mode: bool,
+    /// Shows the game in fullscreen, toggled with a hotkey.
+    pub fullscreen: bool,
+    /// Shows the path of the player in the previous attempts at a level.
+    pub ghost_trails: bool,
+    /// Pauses the gameplay music in the pause menu.
+    pub pause_audio: bool,
+    /// Skips shaking, scaling and camera gliding effects, for players prone to motion sickness.
+    pub reduce_motion: bool,
+    /// Renders the gameplay at a low resolution with crisp pixels.
+    pub retro_mode: bool,
+    /// Scales the speed of particles, levels are authored for [`Difficulty::Normal`].
+    pub difficulty: Difficulty,
+    /// How slow the game gets while aiming, lower gives more time to aim.
+    pub slow_motion: SlowMotion,
+}
+
+impl Settings {
+    #[cfg(not(target_family = "wasm"))]
+    fn load() -> Self {
+        let path = settings_path();
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!("Failed to read settings from `{}`: {err}", path.display());
+                return Self::default();
+            }
+        };
+
+        ron::de::from_bytes(&bytes).unwrap_or_else(|err| {
+            warn!("Failed to parse settings from `{}`: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn load() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save_settings(settings: Res<Settings>) {
+    let path = settings_path();
+    let result = ron::ser::to_string_pretty(settings.as_ref(), ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())
+        .and_then(|ron| std::fs::write(&path, ron).map_err(|err| err.to_string()));
+
+    if let Err(err) = result {
+        warn!("Failed to save settings to `{}`: {err}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let settings: Settings = ron::de::from_str("()").unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+}
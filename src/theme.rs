@@ -9,7 +9,11 @@ pub mod widget;
 
 #[allow(unused_imports)]
 pub mod prelude {
-    pub use super::{interaction::InteractionPalette, palette as ui_palette, widget};
+    pub use super::{
+        interaction::InteractionPalette,
+        palette::{Theme, Themed},
+        widget,
+    };
 }
 
 use bevy::prelude::*;
@@ -17,7 +21,7 @@ use bevy::prelude::*;
 use crate::{asset_tracking::LoadResource, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, palette::plugin));
 
     app.register_type::<Fonts>();
     app.load_resource::<Fonts>();
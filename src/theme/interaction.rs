@@ -1,21 +1,26 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{
+    asset_tracking::LoadResource, audio::sound_effect, settings::Settings, theme::palette::Theme,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
-    app.add_systems(Update, apply_interaction_palette);
+    app.add_systems(Update, animate_interaction);
 
     app.register_type::<InteractionAssets>();
     app.load_resource::<InteractionAssets>();
+    app.init_resource::<LastHoverSound>();
     app.add_observer(play_on_hover_sound_effect);
     app.add_observer(play_on_click_sound_effect);
 }
 
 /// Palette for widget interactions. Add this to an entity that supports
-/// [`Interaction`]s, such as a button, to change its [`BackgroundColor`] based
+/// [`Interaction`]s, such as a button, to animate its [`BackgroundColor`] and scale based
 /// on the current interaction state.
-#[derive(Component, Debug, Reflect)]
+#[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct InteractionPalette {
     pub none: Color,
@@ -23,19 +28,61 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
-fn apply_interaction_palette(
-    mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut BackgroundColor),
-        Changed<Interaction>,
-    >,
+impl InteractionPalette {
+    pub fn color(&self, interaction: &Interaction) -> Color {
+        match interaction {
+            Interaction::None => self.none,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
+        }
+    }
+}
+
+/// Below this distance the animation snaps to its target, so it settles instead of
+/// changing every frame.
+const SNAP_DISTANCE: f32 = 0.001;
+
+fn animate_interaction(
+    time: Res<Time<Real>>,
+    theme: Res<Theme>,
+    settings: Res<Settings>,
+    mut palette_query: Query<(
+        &Interaction,
+        &InteractionPalette,
+        &mut BackgroundColor,
+        &mut Transform,
+    )>,
 ) {
-    for (interaction, palette, mut background) in &mut palette_query {
-        *background = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
-            Interaction::Pressed => palette.pressed,
+    let animation = theme.button_animation;
+    let factor = animation.blend_factor(time.delta_secs());
+
+    for (interaction, palette, mut background, mut transform) in &mut palette_query {
+        let target_color = palette.color(interaction);
+        if background.0 != target_color {
+            let target = target_color.to_linear().to_vec4();
+            let next = background.0.to_linear().to_vec4().lerp(target, factor);
+            background.0 = if next.distance(target) < SNAP_DISTANCE {
+                target_color
+            } else {
+                LinearRgba::from_vec4(next).into()
+            };
+        }
+
+        // Buttons only change their color with reduced motion.
+        let scale = if settings.reduce_motion {
+            1.0
+        } else {
+            animation.scale(interaction)
+        };
+        let target_scale = Vec3::new(scale, scale, 1.0);
+        if transform.scale != target_scale {
+            let next = transform.scale.lerp(target_scale, factor);
+            transform.scale = if next.distance(target_scale) < SNAP_DISTANCE {
+                target_scale
+            } else {
+                next
+            };
         }
-        .into();
     }
 }
 
@@ -58,19 +105,40 @@ impl FromWorld for InteractionAssets {
     }
 }
 
+/// Hover sounds closer together than this are skipped, so sweeping over a grid of buttons doesn't
+/// spam them.
+const HOVER_SOUND_COOLDOWN: Duration = Duration::from_millis(80);
+
+/// When the last hover sound was played, in real time since startup.
+#[derive(Resource, Default)]
+struct LastHoverSound(Option<Duration>);
+
 fn play_on_hover_sound_effect(
     trigger: Trigger<Pointer<Over>>,
     mut commands: Commands,
     interaction_assets: Option<Res<InteractionAssets>>,
     interaction_query: Query<(), With<Interaction>>,
+    time: Res<Time<Real>>,
+    mut last_hover_sound: ResMut<LastHoverSound>,
 ) {
     let Some(interaction_assets) = interaction_assets else {
         return;
     };
 
-    if interaction_query.contains(trigger.target()) {
-        commands.spawn(sound_effect(interaction_assets.hover.clone()));
+    if !interaction_query.contains(trigger.target()) {
+        return;
     }
+
+    let now = time.elapsed();
+    if last_hover_sound
+        .0
+        .is_some_and(|last| now.saturating_sub(last) < HOVER_SOUND_COOLDOWN)
+    {
+        return;
+    }
+
+    last_hover_sound.0 = Some(now);
+    commands.spawn(sound_effect(interaction_assets.hover.clone()));
 }
 
 fn play_on_click_sound_effect(
@@ -1,16 +1,264 @@
+//! The colors of the game, grouped in the [`Theme`] resource.
+//!
+//! UI entities tagged with [`Themed`] follow the current theme, so the whole look can be swapped at once.
+
 use bevy::prelude::*;
 
+use crate::{settings::Settings, theme::interaction::InteractionPalette};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Theme>();
+    app.register_type::<Themed>();
+    app.init_resource::<Theme>();
+
+    app.add_systems(
+        Update,
+        (
+            select_theme.run_if(resource_changed::<Settings>),
+            (
+                apply_theme,
+                apply_clear_color.run_if(resource_changed::<Theme>),
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// #282828
+const BACKGROUND: Color = Color::srgb(0.157, 0.157, 0.157);
+
 /// #ddd369
-pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
+const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 
 /// #fcfbcc
-pub const HEADER_TEXT: Color = Color::srgb(1.0, 1.0, 1.0);
+const HEADER_TEXT: Color = Color::srgb(1.0, 1.0, 1.0);
 
 /// #ececec
-pub const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
+const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
 /// #4666bf
-pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
+const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 /// #6299d1
-pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
+const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
-pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #ffffff
+const LEVEL_BUTTON_TEXT: Color = Color::srgb(1.0, 1.0, 1.0);
+/// #0f0f0f
+const LEVEL_BUTTON_BACKGROUND: Color = Color::srgb(0.059, 0.059, 0.059);
+/// #000000
+const LEVEL_BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.0, 0.0, 0.0);
+/// #000000
+const LEVEL_BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.0, 0.0, 0.0);
+
+/// #f7bd1d
+const INVINCIBLE: Color = Color::srgb(0.969, 0.741, 0.114);
+/// #ffffff, stands out by brightness instead of hue.
+const INVINCIBLE_COLORBLIND: Color = Color::srgb(1.0, 1.0, 1.0);
+/// #000000
+const KILLER_OUTLINE: Color = Color::srgb(0.0, 0.0, 0.0);
+
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct Theme {
+    /// The clear color behind everything.
+    pub background: Color,
+    pub label_text: Color,
+    pub header_text: Color,
+    pub button: ButtonColors,
+    pub level_button: ButtonColors,
+    pub hazard: HazardColors,
+    pub button_animation: ButtonAnimation,
+}
+
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct ButtonColors {
+    pub text: Color,
+    pub background: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+}
+
+/// How buttons animate towards their [`InteractionPalette`] color and scale.
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct ButtonAnimation {
+    /// Roughly how long it takes to reach the target, in seconds.
+    pub duration: f32,
+    pub hovered_scale: f32,
+    pub pressed_scale: f32,
+}
+
+impl ButtonAnimation {
+    pub fn scale(&self, interaction: &Interaction) -> f32 {
+        match interaction {
+            Interaction::None => 1.0,
+            Interaction::Hovered => self.hovered_scale,
+            Interaction::Pressed => self.pressed_scale,
+        }
+    }
+
+    /// How far to move towards the target in a frame that took `delta_secs`.
+    pub fn blend_factor(&self, delta_secs: f32) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        // Covers about 98% of the way in `duration`.
+        1.0 - (-4.0 * delta_secs / self.duration).exp()
+    }
+}
+
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct HazardColors {
+    pub invincible: Color,
+    pub killer_outline: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: BACKGROUND,
+            label_text: LABEL_TEXT,
+            header_text: HEADER_TEXT,
+            button: ButtonColors {
+                text: BUTTON_TEXT,
+                background: BUTTON_BACKGROUND,
+                hovered: BUTTON_HOVERED_BACKGROUND,
+                pressed: BUTTON_PRESSED_BACKGROUND,
+            },
+            level_button: ButtonColors {
+                text: LEVEL_BUTTON_TEXT,
+                background: LEVEL_BUTTON_BACKGROUND,
+                hovered: LEVEL_BUTTON_HOVERED_BACKGROUND,
+                pressed: LEVEL_BUTTON_PRESSED_BACKGROUND,
+            },
+            hazard: HazardColors {
+                invincible: INVINCIBLE,
+                killer_outline: KILLER_OUTLINE,
+            },
+            button_animation: ButtonAnimation {
+                duration: 0.15,
+                hovered_scale: 1.05,
+                pressed_scale: 0.95,
+            },
+        }
+    }
+}
+
+impl Theme {
+    /// The default theme with hazard colors that are easier to tell apart.
+    pub fn colorblind() -> Self {
+        let mut theme = Self::default();
+        theme.hazard.invincible = INVINCIBLE_COLORBLIND;
+        theme
+    }
+}
+
+/// Which [`Theme`] color a UI entity uses.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum Themed {
+    LabelText,
+    HeaderText,
+    ButtonText,
+    LevelButtonText,
+    /// Sets the [`InteractionPalette`] of a button.
+    Button,
+    /// Sets the [`InteractionPalette`] of a level button.
+    LevelButton,
+}
+
+impl Themed {
+    fn text_color(self, theme: &Theme) -> Option<Color> {
+        match self {
+            Self::LabelText => Some(theme.label_text),
+            Self::HeaderText => Some(theme.header_text),
+            Self::ButtonText => Some(theme.button.text),
+            Self::LevelButtonText => Some(theme.level_button.text),
+            Self::Button | Self::LevelButton => None,
+        }
+    }
+
+    fn button_colors(self, theme: &Theme) -> Option<ButtonColors> {
+        match self {
+            Self::Button => Some(theme.button),
+            Self::LevelButton => Some(theme.level_button),
+            _ => None,
+        }
+    }
+}
+
+fn select_theme(settings: Res<Settings>, mut theme: ResMut<Theme>) {
+    *theme = if settings.colorblind_mode {
+        Theme::colorblind()
+    } else {
+        Theme::default()
+    };
+}
+
+fn apply_theme(
+    theme: Res<Theme>,
+    mut query: Query<(
+        Ref<Themed>,
+        Option<&mut TextColor>,
+        Option<(&mut InteractionPalette, &mut BackgroundColor, &Interaction)>,
+    )>,
+) {
+    for (themed, text_color, button) in &mut query {
+        if !theme.is_changed() && !themed.is_added() {
+            continue;
+        }
+
+        if let (Some(color), Some(mut text_color)) = (themed.text_color(&theme), text_color) {
+            text_color.0 = color;
+        }
+
+        if let (Some(colors), Some((mut palette, mut background, interaction))) =
+            (themed.button_colors(&theme), button)
+        {
+            *palette = InteractionPalette {
+                none: colors.background,
+                hovered: colors.hovered,
+                pressed: colors.pressed,
+            };
+            background.0 = palette.color(interaction);
+        }
+    }
+}
+
+fn apply_clear_color(theme: Res<Theme>, mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = theme.background;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buttons_follow_theme_changes() {
+        let mut app = App::new();
+        app.init_resource::<Theme>();
+        app.add_systems(Update, apply_theme);
+
+        let button = app
+            .world_mut()
+            .spawn((
+                Themed::Button,
+                InteractionPalette::default(),
+                BackgroundColor::default(),
+                Interaction::Hovered,
+            ))
+            .id();
+
+        app.update();
+        let background = app.world().get::<BackgroundColor>(button).unwrap().0;
+        assert_eq!(background, BUTTON_HOVERED_BACKGROUND);
+
+        let hovered = Color::srgb(1.0, 0.0, 0.0);
+        app.world_mut().resource_mut::<Theme>().button.hovered = hovered;
+
+        app.update();
+        let background = app.world().get::<BackgroundColor>(button).unwrap().0;
+        assert_eq!(background, hovered);
+    }
+}
@@ -8,7 +8,7 @@ use bevy::{
     ui::Val::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::theme::{interaction::InteractionPalette, palette::Themed};
 
 use super::RegularFont;
 
@@ -38,7 +38,7 @@ pub fn header(text: impl Into<String>) -> impl Bundle {
         Text(text.into()),
         TextFont::from_font_size(40.0),
         RegularFont,
-        TextColor(HEADER_TEXT),
+        Themed::HeaderText,
     )
 }
 
@@ -49,7 +49,7 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
         Text(text.into()),
         TextFont::from_font_size(24.0),
         RegularFont,
-        TextColor(LABEL_TEXT),
+        Themed::LabelText,
     )
 }
 
@@ -117,18 +117,14 @@ where
                 .spawn((
                     Name::new("Button Inner"),
                     Button,
-                    BackgroundColor(BUTTON_BACKGROUND),
-                    InteractionPalette {
-                        none: BUTTON_BACKGROUND,
-                        hovered: BUTTON_HOVERED_BACKGROUND,
-                        pressed: BUTTON_PRESSED_BACKGROUND,
-                    },
+                    InteractionPalette::default(),
+                    Themed::Button,
                     children![(
                         Name::new("Button Text"),
                         Text(text),
                         TextFont::from_font_size(40.0),
                         RegularFont,
-                        TextColor(BUTTON_TEXT),
+                        Themed::ButtonText,
                         // Don't bubble picking events from the text up to the button.
                         Pickable::IGNORE,
                     )],
@@ -0,0 +1,70 @@
+//! Sets the window icon from an image asset.
+//!
+//! Bevy doesn't expose window icons yet, so the icon is set on the winit window directly.
+
+use bevy::{asset::LoadState, prelude::*, window::PrimaryWindow, winit::WinitWindows};
+use winit::window::Icon;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, load_window_icon);
+    app.add_systems(
+        Update,
+        apply_window_icon.run_if(resource_exists::<WindowIcon>),
+    );
+}
+
+/// Path of the image asset to use as the window icon.
+#[derive(Resource)]
+pub struct WindowIconPath(pub String);
+
+#[derive(Resource)]
+struct WindowIcon(Handle<Image>);
+
+fn load_window_icon(
+    path: Option<Res<WindowIconPath>>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    if let Some(path) = path {
+        commands.insert_resource(WindowIcon(asset_server.load(&path.0)));
+    }
+}
+
+fn apply_window_icon(
+    icon: Res<WindowIcon>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    window: Single<Entity, With<PrimaryWindow>>,
+    winit_windows: NonSend<WinitWindows>,
+    mut commands: Commands,
+) {
+    if let Some(LoadState::Failed(err)) = asset_server.get_load_state(&icon.0) {
+        warn!("Failed to load the window icon: {err}");
+        commands.remove_resource::<WindowIcon>();
+        return;
+    }
+
+    // Wait for both the image and the window to be ready.
+    let Some(image) = images.get(&icon.0) else {
+        return;
+    };
+    let Some(winit_window) = winit_windows.get_window(*window) else {
+        return;
+    };
+    commands.remove_resource::<WindowIcon>();
+
+    let (width, height) = (image.width(), image.height());
+    let icon = image
+        .clone()
+        .try_into_dynamic()
+        .map_err(|err| err.to_string())
+        .and_then(|image| {
+            Icon::from_rgba(image.into_rgba8().into_raw(), width, height)
+                .map_err(|err| err.to_string())
+        });
+
+    match icon {
+        Ok(icon) => winit_window.set_window_icon(Some(icon)),
+        Err(err) => warn!("Failed to set the window icon: {err}"),
+    }
+}